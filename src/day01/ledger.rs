@@ -0,0 +1,99 @@
+use crate::diagnostic::{Diagnostic, ParseError};
+
+// An elf and the calories of each item they carry, in the order they're listed
+#[derive(Debug, PartialEq)]
+pub struct Elf {
+    // Where the elf comes in the ledger, starting at 0
+    pub index: usize,
+    pub calories: Vec<u32>,
+}
+
+impl Elf {
+    pub fn total(&self) -> u32 {
+        self.calories.iter().sum()
+    }
+}
+
+// Read the ledger, one number per line with elves separated by blank lines.
+// Any number of blank lines (or none at all) can come before, between or after the elves.
+pub fn parse_ledger(input: &str) -> Result<Vec<Elf>, Diagnostic> {
+    let mut elves = Vec::new();
+    let mut calories = Vec::new();
+    let mut offset = 0;
+    for line in input.split('\n') {
+        let entry = line.trim();
+        if entry.is_empty() {
+            if !calories.is_empty() {
+                elves.push(Elf { index: elves.len(), calories });
+                calories = Vec::new();
+            }
+        } else {
+            match entry.parse::<u32>() {
+                Ok(n) => calories.push(n),
+                Err(_) => {
+                    let start = offset + line.len() - line.trim_start().len();
+                    return Err(Diagnostic::new(
+                        input,
+                        ParseError {
+                            input: &input[start..],
+                            expected: vec!["a number of calories".to_string()],
+                            contexts: vec!["parse_ledger"],
+                        },
+                    ));
+                }
+            }
+        }
+        offset += line.len() + 1;
+    }
+    // The last elf doesn't need a blank line after it
+    if !calories.is_empty() {
+        elves.push(Elf { index: elves.len(), calories });
+    }
+    Ok(elves)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn test_parse_ledger() {
+        let elves = parse_ledger(TEST_INPUT).unwrap();
+        let totals: Vec<(usize, u32)> = elves.iter().map(|e| (e.index, e.total())).collect();
+        assert_eq!(totals, vec![(0, 6000), (1, 4000), (2, 11000), (3, 24000), (4, 10000)]);
+    }
+
+    #[test]
+    fn test_blank_line_layouts() {
+        let expected = vec![Elf { index: 0, calories: vec![1, 2] }, Elf { index: 1, calories: vec![3] }];
+        assert_eq!(parse_ledger("1\n2\n\n3").unwrap(), expected);
+        assert_eq!(parse_ledger("1\n2\n\n3\n").unwrap(), expected);
+        assert_eq!(parse_ledger("\n\n1\n2\n\n\n\n3\n\n").unwrap(), expected);
+        assert_eq!(parse_ledger("1\r\n2\r\n\r\n3\r\n").unwrap(), expected);
+        assert_eq!(parse_ledger("1\n2\n  \n3").unwrap(), expected);
+        assert_eq!(parse_ledger("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_bad_entry() {
+        let err = parse_ledger("1000\n2000\n\n  3OOO\n4000").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+        assert_eq!(err.expected, vec!["a number of calories"]);
+        assert!(err.to_string().starts_with("error: parse_ledger failed, expected a number of calories"));
+    }
+}
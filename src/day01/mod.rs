@@ -1,2 +1,5 @@
+pub mod ledger;
 pub mod part1;
-
+pub mod ranking;
+pub mod rebalance;
+pub mod statistics;
@@ -1,46 +1,32 @@
+use super::{ledger::parse_ledger, ranking::top_k};
+
+pub const INPUT: &str = include_str!("input.txt");
 
 pub fn solution() {
-    println!("Solution day one part one: {}", part_one());
-    println!("Solution day one part two: {}", part_two());
+    println!("Solution day one part one: {}", part_one(INPUT));
+    println!("Solution day one part two: {}", part_two(INPUT));
 }
 
-fn part_one() -> u32 {
-    let data = get_data();
-    group_elf_inventories(data.as_slice())
-        .iter()
-        .map(|calories| calories.iter().sum()) // Sum each elf's inventory of calories
-        .max()
-        .unwrap()
+pub fn part_one(input: &str) -> u32 {
+    top_three(input)[0]
 }
 
-fn part_two() -> u32 {
-    let data = get_data();
-    let mut elf_calories: Vec<u32> = group_elf_inventories(data.as_slice())
-        .iter()
-        .map(|calories| calories.iter().sum()) // Sum each elf's inventory of calories
-        .collect();
-
-    elf_calories.sort_by(|a, b| b.cmp(a));
-    elf_calories.iter().take(3).sum()
-
+pub fn part_two(input: &str) -> u32 {
+    top_three(input).iter().sum()
 }
 
-fn get_data() -> Vec<Option<u32>> {
-    let input = include_str!("input.txt");
-    input.split('\n').map(|n| n.parse::<u32>().ok()).collect()
+fn top_three(input: &str) -> Vec<u32> {
+    let elves = parse_ledger(input).unwrap_or_else(|e| panic!("{}", e));
+    top_k(&elves, 3).iter().map(|r| r.total).collect()
 }
 
-fn group_elf_inventories(calorie_list: &[Option<u32>]) -> Vec<Vec<u32>> {
-    let mut grouped = Vec::new();
-    let mut temp = Vec::new();
-    for calories in calorie_list {
-        match calories {
-            None => {
-                grouped.push(temp);
-                temp = Vec::new();
-            },
-            Some(n) => temp.push(*n),
-        }
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(part_one(INPUT), 71924);
+        assert_eq!(part_two(INPUT), 210406);
     }
-    grouped
 }
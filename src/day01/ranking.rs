@@ -0,0 +1,71 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::ledger::Elf;
+
+#[derive(Debug, PartialEq)]
+pub struct Ranked {
+    pub index: usize,
+    pub total: u32,
+}
+
+// The k elves carrying the most calories, most first. Elves with the same total
+// are ranked by their place in the ledger, so the earlier elf wins the tie.
+// Only the best k are kept on a heap as the elves go past, so it's O(n log k).
+pub fn top_k(inventories: &[Elf], k: usize) -> Vec<Ranked> {
+    if k == 0 {
+        return Vec::new();
+    }
+    // A min heap of the best so far, the worst of them on top ready to be replaced.
+    // Reverse on the index makes the later elf the lesser of two with the same total.
+    let mut best: BinaryHeap<Reverse<(u32, Reverse<usize>)>> = BinaryHeap::with_capacity(k + 1);
+    for elf in inventories {
+        let candidate = Reverse((elf.total(), Reverse(elf.index)));
+        if best.len() < k {
+            best.push(candidate);
+        } else if best.peek().is_some_and(|worst| candidate < *worst) {
+            best.pop();
+            best.push(candidate);
+        }
+    }
+    best.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((total, Reverse(index)))| Ranked { index, total })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn elves(totals: &[u32]) -> Vec<Elf> {
+        totals.iter().enumerate().map(|(index, t)| Elf { index, calories: vec![*t] }).collect()
+    }
+
+    #[test]
+    fn test_top_k() {
+        let elves = elves(&[6000, 4000, 11000, 24000, 10000]);
+        let top: Vec<(usize, u32)> = top_k(&elves, 3).iter().map(|r| (r.index, r.total)).collect();
+        assert_eq!(top, vec![(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(top_k(&elves, 0), vec![]);
+        assert_eq!(top_k(&elves, 10).len(), 5);
+    }
+
+    #[test]
+    fn test_ties_go_to_the_earlier_elf() {
+        let elves = elves(&[5, 9, 7, 9, 7, 9]);
+        let top: Vec<usize> = top_k(&elves, 4).iter().map(|r| r.index).collect();
+        assert_eq!(top, vec![1, 3, 5, 2]);
+    }
+
+    #[test]
+    fn test_matches_sorting() {
+        // A spread of totals with plenty of ties, checked against a full sort
+        let elves = elves(&(0..500u32).map(|n| (n * 7919) % 101).collect::<Vec<u32>>());
+        let mut sorted: Vec<(u32, usize)> = elves.iter().map(|e| (e.total(), e.index)).collect();
+        sorted.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        for k in [1, 3, 50, 499, 500] {
+            let top: Vec<(u32, usize)> = top_k(&elves, k).iter().map(|r| (r.total, r.index)).collect();
+            assert_eq!(top, sorted[..k]);
+        }
+    }
+}
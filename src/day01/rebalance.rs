@@ -0,0 +1,269 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+use super::ledger::Elf;
+
+// Above this many items the exact search can take too long, so plan uses LPT
+pub const EXACT_LIMIT: usize = 20;
+
+// One snack handed from one elf to another
+#[derive(Debug, PartialEq)]
+pub struct Move {
+    // The elves' indices, as in the ledger
+    pub from: usize,
+    pub to: usize,
+    // Where the snack is in the giving elf's inventory
+    pub item: usize,
+    pub calories: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Method {
+    Exact,
+    // Longest processing time first, each snack (biggest first) goes to the least loaded elf
+    Lpt,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Plan {
+    pub method: Method,
+    pub moves: Vec<Move>,
+    // Each elf's total after the moves, in ledger order
+    pub loads: Vec<u32>,
+    // No plan can do better than this, the bigger of the fair share and the biggest snack
+    pub lower_bound: u32,
+    // The largest the plan's max load can be, as a multiple of the best possible.
+    // 1 for the exact solver, and 4/3 - 1/3m for LPT with m elves (Graham, 1969).
+    pub guarantee: f64,
+}
+
+impl Plan {
+    pub fn max_load(&self) -> u32 {
+        self.loads.iter().copied().max().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for m in &self.moves {
+            writeln!(f, "elf {} gives item {} ({} calories) to elf {}", m.from, m.item, m.calories, m.to)?;
+        }
+        let method = match self.method {
+            Method::Exact => "exact",
+            Method::Lpt => "LPT",
+        };
+        writeln!(f, "{} moves ({}), most carried is now {}", self.moves.len(), method, self.max_load())?;
+        writeln!(f, "No plan can do better than {}, and this one is within {:.3}x of the best", self.lower_bound, self.guarantee)
+    }
+}
+
+// A snack, and the elf (by position) that has it to start with
+#[derive(Clone, Copy)]
+struct Snack {
+    owner: usize,
+    item: usize,
+    calories: u32,
+}
+
+// Move snacks between the elves so that the most any of them carries is as small as it
+// can be. Solved exactly for small inventories, and with LPT for the rest.
+pub fn plan(elves: &[Elf]) -> Plan {
+    let snacks = elves.iter().map(|e| e.calories.len()).sum::<usize>();
+    if snacks <= EXACT_LIMIT {
+        plan_exact(elves)
+    } else {
+        plan_lpt(elves)
+    }
+}
+
+pub fn plan_lpt(elves: &[Elf]) -> Plan {
+    let snacks = get_snacks(elves);
+    let mut least_loaded: BinaryHeap<Reverse<(u32, usize)>> = (0..elves.len()).map(|b| Reverse((0, b))).collect();
+    let mut bins = vec![Vec::new(); elves.len()];
+    for snack in snacks {
+        if let Some(Reverse((load, b))) = least_loaded.pop() {
+            bins[b].push(snack);
+            least_loaded.push(Reverse((load + snack.calories, b)));
+        }
+    }
+    let m = elves.len().max(1) as f64;
+    to_plan(elves, bins, Method::Lpt, 4.0 / 3.0 - 1.0 / (3.0 * m))
+}
+
+// Branch and bound over every way to share out the snacks, starting from the LPT plan
+pub fn plan_exact(elves: &[Elf]) -> Plan {
+    let snacks = get_snacks(elves);
+    let lpt = plan_lpt(elves);
+    let mut search = Search {
+        snacks: &snacks,
+        lower_bound: lpt.lower_bound,
+        best: lpt.max_load(),
+        best_bins: None,
+        bins: vec![Vec::new(); elves.len()],
+        loads: vec![0; elves.len()],
+    };
+    search.run(0);
+    match search.best_bins {
+        Some(bins) => to_plan(elves, bins, Method::Exact, 1.0),
+        // LPT was already as good as it gets
+        None => Plan { method: Method::Exact, guarantee: 1.0, ..lpt },
+    }
+}
+
+struct Search<'a> {
+    snacks: &'a [Snack],
+    lower_bound: u32,
+    // The smallest max load found so far, and the bins that gave it (if it beat LPT)
+    best: u32,
+    best_bins: Option<Vec<Vec<Snack>>>,
+    bins: Vec<Vec<Snack>>,
+    loads: Vec<u32>,
+}
+
+impl Search<'_> {
+    fn run(&mut self, next: usize) {
+        if self.best == self.lower_bound {
+            return;
+        }
+        let Some(snack) = self.snacks.get(next).copied() else {
+            self.best = self.loads.iter().copied().max().unwrap_or(0);
+            self.best_bins = Some(self.bins.clone());
+            return;
+        };
+        for b in 0..self.bins.len() {
+            // Putting the snack with an elf carrying the same as one already tried gives the same plans
+            if self.loads[..b].contains(&self.loads[b]) || self.loads[b] + snack.calories >= self.best {
+                continue;
+            }
+            self.loads[b] += snack.calories;
+            self.bins[b].push(snack);
+            self.run(next + 1);
+            self.bins[b].pop();
+            self.loads[b] -= snack.calories;
+        }
+    }
+}
+
+// Every snack, biggest first
+fn get_snacks(elves: &[Elf]) -> Vec<Snack> {
+    let mut snacks: Vec<Snack> = elves
+        .iter()
+        .enumerate()
+        .flat_map(|(owner, e)| e.calories.iter().enumerate().map(move |(item, c)| Snack { owner, item, calories: *c }))
+        .collect();
+    snacks.sort_by_key(|s| Reverse(s.calories));
+    snacks
+}
+
+// The bins are loads waiting for an elf. Hand each one to the elf that already has
+// most of its snacks (greedily, biggest overlap first), so that few snacks need to move.
+fn to_plan(elves: &[Elf], bins: Vec<Vec<Snack>>, method: Method, guarantee: f64) -> Plan {
+    let mut overlaps: Vec<(usize, u32, usize, usize)> = Vec::new();
+    for (b, bin) in bins.iter().enumerate() {
+        for owner in 0..elves.len() {
+            let kept: Vec<u32> = bin.iter().filter(|s| s.owner == owner).map(|s| s.calories).collect();
+            overlaps.push((kept.len(), kept.iter().sum(), b, owner));
+        }
+    }
+    // Most snacks kept, then most calories kept (the less there is to carry about)
+    overlaps.sort_by_key(|(kept, calories, b, owner)| (Reverse(*kept), Reverse(*calories), *b, *owner));
+
+    let mut elf_for_bin = vec![None; bins.len()];
+    let mut taken = vec![false; elves.len()];
+    for (_, _, b, owner) in overlaps {
+        if elf_for_bin[b].is_none() && !taken[owner] {
+            elf_for_bin[b] = Some(owner);
+            taken[owner] = true;
+        }
+    }
+
+    let mut loads = vec![0; elves.len()];
+    let mut moves = Vec::new();
+    for (b, bin) in bins.iter().enumerate() {
+        let to = elf_for_bin[b].unwrap_or(b);
+        loads[to] = bin.iter().map(|s| s.calories).sum();
+        for s in bin.iter().filter(|s| s.owner != to) {
+            moves.push(Move { from: elves[s.owner].index, to: elves[to].index, item: s.item, calories: s.calories });
+        }
+    }
+    moves.sort_by_key(|m| (m.from, m.item));
+
+    let total: u32 = elves.iter().map(|e| e.total()).sum();
+    let biggest = elves.iter().flat_map(|e| e.calories.iter().copied()).max().unwrap_or(0);
+    let fair_share = total.div_ceil(elves.len().max(1) as u32);
+    Plan { method, moves, loads, lower_bound: fair_share.max(biggest), guarantee }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day01::ledger::parse_ledger;
+
+    const TEST_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    // Carry out the moves, checking that each snack is where the plan says
+    fn apply(elves: &[Elf], moves: &[Move]) -> Vec<u32> {
+        let mut loads: Vec<u32> = elves.iter().map(|e| e.total()).collect();
+        for m in moves {
+            assert_eq!(elves[m.from].calories[m.item], m.calories);
+            loads[m.from] -= m.calories;
+            loads[m.to] += m.calories;
+        }
+        loads
+    }
+
+    fn elves(inventories: &[&[u32]]) -> Vec<Elf> {
+        inventories.iter().enumerate().map(|(index, c)| Elf { index, calories: c.to_vec() }).collect()
+    }
+
+    #[test]
+    fn test_plan() {
+        let elves = parse_ledger(TEST_INPUT).unwrap();
+        let plan = plan(&elves);
+        assert_eq!(plan.method, Method::Exact);
+        assert_eq!(plan.loads, vec![11000; 5]);
+        assert_eq!(plan.lower_bound, 11000);
+        assert_eq!(apply(&elves, &plan.moves), plan.loads);
+        // Only 5000 and 6000 are already together, and 10000, 9000 and 4000 stay put
+        assert_eq!(plan.moves.len(), 4);
+    }
+
+    #[test]
+    fn test_exact_beats_lpt() {
+        let elves = elves(&[&[3, 3, 2, 2, 2], &[]]);
+        let lpt = plan_lpt(&elves);
+        assert_eq!(lpt.max_load(), 7);
+        // LPT's guarantee is tight here, 7/6 of the best
+        assert!(lpt.max_load() as f64 <= lpt.guarantee * 6.0 + 1e-9);
+
+        let exact = plan_exact(&elves);
+        assert_eq!(exact.max_load(), 6);
+        assert_eq!(exact.lower_bound, 6);
+        assert_eq!(apply(&elves, &exact.moves), exact.loads);
+        // The elf keeps the three 2s and hands over the 3s, two moves rather than three
+        assert_eq!(exact.moves.len(), 2);
+        assert!(exact.moves.iter().all(|m| m.calories == 3 && m.to == 1));
+    }
+
+    #[test]
+    fn test_large_inputs_use_lpt() {
+        let inventories: Vec<Vec<u32>> = (0..10).map(|e| (1..=e * 2).map(|n| n * 100 + e).collect()).collect();
+        let elves = elves(&inventories.iter().map(|v| v.as_slice()).collect::<Vec<&[u32]>>());
+        let plan = plan(&elves);
+        assert_eq!(plan.method, Method::Lpt);
+        assert_eq!(apply(&elves, &plan.moves), plan.loads);
+        assert!(plan.max_load() as f64 <= plan.guarantee * plan.lower_bound as f64 + 1e-9);
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+
+use super::ledger::Elf;
+
+const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+const HISTOGRAM_BUCKETS: u32 = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+#[derive(Debug, PartialEq)]
+pub struct Statistics {
+    // How many items each elf carries, in ledger order
+    pub item_counts: Vec<usize>,
+    pub mean: f64,
+    pub median: f64,
+    // Nearest rank percentiles of the totals, as (percentile, total)
+    pub percentiles: Vec<(u8, u32)>,
+    pub histogram: Vec<Bucket>,
+    pub outliers: Vec<Outlier>,
+}
+
+// The number of elves with a total from `from` up to and including `to`
+#[derive(Debug, PartialEq)]
+pub struct Bucket {
+    pub from: u32,
+    pub to: u32,
+    pub elves: usize,
+}
+
+// An elf can be an outlier for its total and for its number of items
+#[derive(Debug, PartialEq)]
+pub enum Outlier {
+    NoItems { index: usize },
+    // Further than one and a half interquartile ranges outside the middle half
+    Low { index: usize, total: u32 },
+    High { index: usize, total: u32 },
+    FewItems { index: usize, items: usize },
+    ManyItems { index: usize, items: usize },
+}
+
+// Summarise the elves' totals, None if there are no elves to summarise
+pub fn statistics(elves: &[Elf]) -> Option<Statistics> {
+    if elves.is_empty() {
+        return None;
+    }
+    let totals: Vec<u32> = elves.iter().map(|e| e.total()).collect();
+    let mut sorted = totals.clone();
+    sorted.sort();
+
+    let n = sorted.len();
+    let median = if n.is_multiple_of(2) {
+        (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0
+    } else {
+        sorted[n / 2] as f64
+    };
+
+    Some(Statistics {
+        item_counts: elves.iter().map(|e| e.calories.len()).collect(),
+        mean: totals.iter().map(|t| *t as f64).sum::<f64>() / n as f64,
+        median,
+        percentiles: PERCENTILES.iter().map(|p| (*p, percentile(&sorted, *p))).collect(),
+        histogram: histogram(&sorted),
+        outliers: outliers(elves, &sorted),
+    })
+}
+
+// Nearest rank, the smallest total that at least p percent of the elves are at or below
+fn percentile(sorted: &[u32], p: u8) -> u32 {
+    let rank = (p as usize * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+fn histogram(sorted: &[u32]) -> Vec<Bucket> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = ((max - min) / HISTOGRAM_BUCKETS) + 1;
+    let mut buckets: Vec<Bucket> = (0..HISTOGRAM_BUCKETS)
+        .map(|b| min + b * width)
+        .take_while(|from| *from <= max)
+        .map(|from| Bucket { from, to: from + width - 1, elves: 0 })
+        .collect();
+    for total in sorted {
+        buckets[((total - min) / width) as usize].elves += 1;
+    }
+    buckets
+}
+
+// Anything below the first or above the second is an outlier
+fn fences(sorted: &[u32]) -> (f64, f64) {
+    let (q1, q3) = (percentile(sorted, 25) as f64, percentile(sorted, 75) as f64);
+    let fence = 1.5 * (q3 - q1);
+    (q1 - fence, q3 + fence)
+}
+
+fn outliers(elves: &[Elf], sorted: &[u32]) -> Vec<Outlier> {
+    let (low, high) = fences(sorted);
+    let mut item_counts: Vec<u32> = elves.iter().map(|e| e.calories.len() as u32).collect();
+    item_counts.sort();
+    let (few, many) = fences(&item_counts);
+
+    let mut outliers = Vec::new();
+    for elf in elves {
+        let (index, total, items) = (elf.index, elf.total(), elf.calories.len());
+        // With nothing to carry the total and the count say the same thing
+        if items == 0 {
+            outliers.push(Outlier::NoItems { index });
+            continue;
+        }
+        if (total as f64) < low {
+            outliers.push(Outlier::Low { index, total });
+        } else if (total as f64) > high {
+            outliers.push(Outlier::High { index, total });
+        }
+        if (items as f64) < few {
+            outliers.push(Outlier::FewItems { index, items });
+        } else if (items as f64) > many {
+            outliers.push(Outlier::ManyItems { index, items });
+        }
+    }
+    outliers
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = &self.item_counts;
+        writeln!(f, "Elves: {}", items.len())?;
+        writeln!(
+            f,
+            "Items per elf: min {}, max {}, mean {:.1}",
+            items.iter().min().unwrap_or(&0),
+            items.iter().max().unwrap_or(&0),
+            items.iter().sum::<usize>() as f64 / items.len() as f64
+        )?;
+        writeln!(f, "Calories: mean {:.1}, median {:.1}", self.mean, self.median)?;
+        let percentiles: Vec<String> = self.percentiles.iter().map(|(p, t)| format!("p{} {}", p, t)).collect();
+        writeln!(f, "Percentiles: {}", percentiles.join(", "))?;
+
+        writeln!(f, "Histogram:")?;
+        let most = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0).max(1);
+        let label_width = self.histogram.iter().map(|b| format!("{}-{}", b.from, b.to).len()).max().unwrap_or(0);
+        for b in &self.histogram {
+            let bar = "#".repeat((b.elves * HISTOGRAM_WIDTH).div_ceil(most));
+            writeln!(f, "  {:>w$} | {} {}", format!("{}-{}", b.from, b.to), bar, b.elves, w = label_width)?;
+        }
+
+        if self.outliers.is_empty() {
+            return writeln!(f, "Outliers: none");
+        }
+        writeln!(f, "Outliers:")?;
+        for o in &self.outliers {
+            match o {
+                Outlier::NoItems { index } => writeln!(f, "  elf {} carries no items", index)?,
+                Outlier::Low { index, total } => writeln!(f, "  elf {} carries only {} calories", index, total)?,
+                Outlier::High { index, total } => writeln!(f, "  elf {} carries {} calories", index, total)?,
+                Outlier::FewItems { index, items } => writeln!(f, "  elf {} carries only {} items", index, items)?,
+                Outlier::ManyItems { index, items } => writeln!(f, "  elf {} carries {} items", index, items)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day01::ledger::parse_ledger;
+
+    const TEST_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn test_statistics() {
+        let stats = statistics(&parse_ledger(TEST_INPUT).unwrap()).unwrap();
+        assert_eq!(stats.item_counts, vec![3, 1, 2, 3, 1]);
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert_eq!(stats.percentiles, vec![(10, 4000), (25, 6000), (50, 10000), (75, 11000), (90, 24000)]);
+        assert_eq!(stats.histogram[0], Bucket { from: 4000, to: 6000, elves: 2 });
+        assert_eq!(stats.histogram.iter().map(|b| b.elves).sum::<usize>(), 5);
+        assert_eq!(stats.histogram.last().unwrap().to, 24009);
+        assert_eq!(stats.outliers, vec![Outlier::High { index: 3, total: 24000 }]);
+        assert_eq!(statistics(&[]), None);
+    }
+
+    #[test]
+    fn test_outliers() {
+        // Eight elves with 150 calories each, then one with a single snack
+        let ledger = format!("{}1\n", "100\n50\n\n".repeat(8));
+        let stats = statistics(&parse_ledger(&ledger).unwrap()).unwrap();
+        assert_eq!(stats.median, 150.0);
+        assert_eq!(stats.outliers, vec![Outlier::Low { index: 8, total: 1 }, Outlier::FewItems { index: 8, items: 1 }]);
+
+        // The ledger can't have an elf with no items, but statistics takes any elves
+        let mut elves: Vec<Elf> = (0..8).map(|index| Elf { index, calories: vec![100, 50] }).collect();
+        elves.push(Elf { index: 8, calories: vec![] });
+        // Lots of small snacks, an ordinary total but far more items than anyone else
+        elves.push(Elf { index: 9, calories: vec![10; 15] });
+        let stats = statistics(&elves).unwrap();
+        assert_eq!(stats.outliers, vec![Outlier::NoItems { index: 8 }, Outlier::ManyItems { index: 9, items: 15 }]);
+        assert!(stats.to_string().ends_with("  elf 8 carries no items\n  elf 9 carries 15 items\n"));
+    }
+
+    #[test]
+    fn test_display() {
+        let report = statistics(&parse_ledger(TEST_INPUT).unwrap()).unwrap().to_string();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[1], "Items per elf: min 1, max 3, mean 2.0");
+        assert_eq!(lines[3], "Percentiles: p10 4000, p25 6000, p50 10000, p75 11000, p90 24000");
+        assert_eq!(lines[5], "    4000-6000 | ######################################## 2");
+        assert!(report.ends_with("Outliers:\n  elf 3 carries 24000 calories\n"));
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+use super::rules::{Game, Outcome, Response, Rules, RulesError, ShapeId};
+
+const OPPONENT_LETTERS: &str = "ABC";
+const PLAYER_LETTERS: [char; 3] = ['X', 'Y', 'Z'];
+
+// One way of reading the second column of the guide, and the score it gives
+#[derive(Debug, PartialEq)]
+pub struct Evaluation {
+    pub description: String,
+    pub score: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Analysis {
+    // Every shape mapping, then the outcome reading from part two
+    pub evaluations: Vec<Evaluation>,
+}
+
+impl Analysis {
+    // The reading with the highest score, the first of them if there's a tie
+    pub fn best(&self) -> Option<&Evaluation> {
+        self.evaluations.iter().rev().max_by_key(|e| e.score)
+    }
+
+    pub fn matching(&self, target: u32) -> Vec<&Evaluation> {
+        self.evaluations.iter().filter(|e| e.score == target).collect()
+    }
+}
+
+// Score the guide under every way X, Y and Z could map to Rock, Paper and Scissors,
+// and under the reading where they mean lose, draw and win
+pub fn analyse(guide: &[(char, char)]) -> Result<Analysis, String> {
+    let game = Game::rock_paper_scissors();
+    let mut readings: Vec<Rules> = permutations(game.shapes.len())
+        .into_iter()
+        .map(|shapes| {
+            // with_shapes wants the letters in the order of the shapes they name
+            let letters: String = (0..shapes.len()).map(|s| PLAYER_LETTERS[shapes.iter().position(|p| *p == s).unwrap()]).collect();
+            Rules::with_shapes(game.clone(), OPPONENT_LETTERS, &letters)
+        })
+        .collect::<Result<Vec<Rules>, RulesError>>()
+        .map_err(|e| e.to_string())?;
+    readings.push(Rules::with_outcomes(game.clone(), OPPONENT_LETTERS, PLAYER_LETTERS).map_err(|e| e.to_string())?);
+
+    let evaluations = readings
+        .iter()
+        .map(|rules| {
+            let score = rules.total_score(guide).map_err(|e| e.to_string())?;
+            Ok(Evaluation { description: describe(rules), score })
+        })
+        .collect::<Result<Vec<Evaluation>, String>>()?;
+    Ok(Analysis { evaluations })
+}
+
+// Every ordering of the shapes, in lexicographic order
+fn permutations(n: usize) -> Vec<Vec<ShapeId>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    (0..n)
+        .flat_map(|first| {
+            permutations(n - 1).into_iter().map(move |rest| {
+                let mut p = vec![first];
+                p.extend(rest.into_iter().map(|s| if s >= first { s + 1 } else { s }));
+                p
+            })
+        })
+        .collect()
+}
+
+fn describe(rules: &Rules) -> String {
+    PLAYER_LETTERS
+        .iter()
+        .map(|c| {
+            let meaning = match rules.response[c] {
+                Response::Shape(s) => rules.game.shapes[s].name,
+                Response::Outcome(Outcome::Loss) => "lose",
+                Response::Outcome(Outcome::Draw) => "draw",
+                Response::Outcome(Outcome::Win) => "win",
+            };
+            format!("{}={}", c, meaning)
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.evaluations.iter().map(|e| e.description.len()).max().unwrap_or(0);
+        for e in &self.evaluations {
+            writeln!(f, "{:<w$}  {:>6}", e.description, e.score, w = width)?;
+        }
+        if let Some(best) = self.best() {
+            writeln!(f, "Best: {} scores {}", best.description, best.score)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GUIDE: [(char, char); 3] = [('A', 'Y'), ('B', 'X'), ('C', 'Z')];
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(
+            permutations(3),
+            vec![vec![0, 1, 2], vec![0, 2, 1], vec![1, 0, 2], vec![1, 2, 0], vec![2, 0, 1], vec![2, 1, 0]]
+        );
+    }
+
+    #[test]
+    fn test_analyse() {
+        let analysis = analyse(&GUIDE).unwrap();
+        assert_eq!(analysis.evaluations.len(), 7);
+        assert_eq!(analysis.evaluations[0], Evaluation { description: "X=Rock, Y=Paper, Z=Scissors".to_string(), score: 15 });
+        assert_eq!(analysis.evaluations[6], Evaluation { description: "X=lose, Y=draw, Z=win".to_string(), score: 12 });
+
+        // X=Scissors, Y=Paper, Z=Rock wins all three rounds, 2 + 3 + 1 + 18
+        let best = analysis.best().unwrap();
+        assert_eq!(best.description, "X=Scissors, Y=Paper, Z=Rock");
+        assert_eq!(best.score, 24);
+
+        let matches: Vec<&str> = analysis.matching(12).iter().map(|e| e.description.as_str()).collect();
+        assert_eq!(matches, vec!["X=lose, Y=draw, Z=win"]);
+        assert!(analysis.matching(1).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_letter() {
+        assert_eq!(analyse(&[('A', 'Q')]), Err("round 1: 'Q' isn't something we can play".to_string()));
+    }
+}
@@ -0,0 +1,141 @@
+use super::rules::{Outcome, RoundError, Rules};
+
+// How one round of the guide played out, with the score so far
+#[derive(Debug, PartialEq)]
+pub struct RoundReport {
+    // Counting from 1, as the rounds are played
+    pub round: usize,
+    pub opponent: &'static str,
+    pub player: &'static str,
+    pub outcome: Outcome,
+    pub shape_points: u32,
+    pub outcome_points: u32,
+    pub total: u32,
+}
+
+// Play the guide under the rules, round by round
+pub fn breakdown(rules: &Rules, guide: &[(char, char)]) -> Result<Vec<RoundReport>, RoundError> {
+    let mut total = 0;
+    let rounds = rules.play_guide(guide)?;
+    Ok(rounds
+        .iter()
+        .enumerate()
+        .map(|(idx, round)| {
+            let shape_points = rules.game.shapes[round.player].value;
+            let outcome_points = rules.game.outcome_points(round.outcome);
+            total += shape_points + outcome_points;
+            RoundReport {
+                round: idx + 1,
+                opponent: rules.game.shapes[round.opponent].name,
+                player: rules.game.shapes[round.player].name,
+                outcome: round.outcome,
+                shape_points,
+                outcome_points,
+                total,
+            }
+        })
+        .collect())
+}
+
+const HEADERS: [&str; 7] = ["round", "opponent", "player", "outcome", "shape points", "outcome points", "total"];
+
+fn fields(r: &RoundReport) -> [String; 7] {
+    let outcome = match r.outcome {
+        Outcome::Loss => "loss",
+        Outcome::Draw => "draw",
+        Outcome::Win => "win",
+    };
+    [
+        r.round.to_string(),
+        r.opponent.to_string(),
+        r.player.to_string(),
+        outcome.to_string(),
+        r.shape_points.to_string(),
+        r.outcome_points.to_string(),
+        r.total.to_string(),
+    ]
+}
+
+// None of the fields can have a comma or a quote in them, so there's nothing to escape
+pub fn to_csv(rounds: &[RoundReport]) -> String {
+    let mut lines = vec![HEADERS.join(",")];
+    lines.extend(rounds.iter().map(|r| fields(r).join(",")));
+    lines.join("\n") + "\n"
+}
+
+// Columns lined up, numbers to the right and names to the left
+pub fn to_text(rounds: &[RoundReport]) -> String {
+    let rows: Vec<[String; 7]> = rounds.iter().map(fields).collect();
+    let widths: Vec<usize> = (0..HEADERS.len())
+        .map(|col| rows.iter().map(|r| r[col].len()).chain([HEADERS[col].len()]).max().unwrap_or(0))
+        .collect();
+    let is_text = |col: usize| (1..=3).contains(&col);
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(col, c)| match is_text(col) {
+                true => format!("{:<w$}", c, w = widths[col]),
+                false => format!("{:>w$}", c, w = widths[col]),
+            })
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let mut lines = vec![format_row(HEADERS.to_vec())];
+    lines.extend(rows.iter().map(|r| format_row(r.iter().map(String::as_str).collect())));
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GUIDE: [(char, char); 3] = [('A', 'Y'), ('B', 'X'), ('C', 'Z')];
+
+    #[test]
+    fn test_breakdown() {
+        let rounds = breakdown(&Rules::part_two(), &GUIDE).unwrap();
+        assert_eq!(
+            rounds[2],
+            RoundReport {
+                round: 3,
+                opponent: "Scissors",
+                player: "Rock",
+                outcome: Outcome::Win,
+                shape_points: 1,
+                outcome_points: 6,
+                total: 12
+            }
+        );
+        let error = breakdown(&Rules::part_one(), &[('A', 'X'), ('B', 'W')]).unwrap_err();
+        assert_eq!(error.to_string(), "round 2: 'W' isn't something we can play");
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let rounds = breakdown(&Rules::part_one(), &GUIDE).unwrap();
+        assert_eq!(
+            to_csv(&rounds),
+            "round,opponent,player,outcome,shape points,outcome points,total
+1,Rock,Paper,win,2,6,8
+2,Paper,Rock,loss,1,0,9
+3,Scissors,Scissors,draw,3,3,15
+"
+        );
+    }
+
+    #[test]
+    fn test_to_text() {
+        let rounds = breakdown(&Rules::part_one(), &GUIDE).unwrap();
+        assert_eq!(
+            to_text(&rounds),
+            "round  opponent  player    outcome  shape points  outcome points  total
+    1  Rock      Paper     win                 2               6      8
+    2  Paper     Rock      loss                1               0      9
+    3  Scissors  Scissors  draw                3               3     15
+"
+        );
+    }
+}
@@ -1 +1,5 @@
+pub mod analysis;
+pub mod breakdown;
+pub mod rules;
 pub mod solution;
+pub mod tournament;
@@ -0,0 +1,309 @@
+use std::{collections::HashMap, fmt};
+
+// A shape's position in the game's cycle
+pub type ShapeId = usize;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Points {
+    pub win: u32,
+    pub draw: u32,
+    pub loss: u32,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Shape {
+    pub name: &'static str,
+    pub value: u32,
+}
+
+// A game where the shapes go round in a cycle, like Rock, Paper, Scissors. There has to be
+// an odd number of them, so that each shape beats the half of the others that come just
+// before it in the cycle, and loses to the half that come just after it.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Game {
+    pub shapes: Vec<Shape>,
+    pub points: Points,
+}
+
+impl Game {
+    pub fn new(shapes: Vec<Shape>, points: Points) -> Result<Self, &'static str> {
+        if shapes.len().is_multiple_of(2) {
+            return Err("A cyclic game needs an odd number of shapes");
+        }
+        Ok(Self { shapes, points })
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        let shapes = vec![
+            Shape { name: "Rock", value: 1 },
+            Shape { name: "Paper", value: 2 },
+            Shape { name: "Scissors", value: 3 },
+        ];
+        Self::new(shapes, Points { win: 6, draw: 3, loss: 0 }).unwrap()
+    }
+
+    pub fn outcome(&self, opponent: ShapeId, player: ShapeId) -> Outcome {
+        let n = self.shapes.len();
+        match (player + n - opponent) % n {
+            0 => Outcome::Draw,
+            d if d <= n / 2 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    pub fn outcome_points(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Win => self.points.win,
+            Outcome::Draw => self.points.draw,
+            Outcome::Loss => self.points.loss,
+        }
+    }
+
+    pub fn score(&self, opponent: ShapeId, player: ShapeId) -> u32 {
+        self.shapes[player].value + self.outcome_points(self.outcome(opponent, player))
+    }
+
+    // The shape that gets the outcome against the opponent. When more than one
+    // does (in games with more than three shapes), the closest in the cycle.
+    pub fn shape_for(&self, opponent: ShapeId, outcome: Outcome) -> ShapeId {
+        let n = self.shapes.len();
+        match outcome {
+            Outcome::Draw => opponent,
+            Outcome::Win => (opponent + 1) % n,
+            Outcome::Loss => (opponent + n - 1) % n,
+        }
+    }
+}
+
+// What a letter in the second column of the strategy guide tells us to do
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Response {
+    Shape(ShapeId),
+    Outcome(Outcome),
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Round {
+    pub opponent: ShapeId,
+    pub player: ShapeId,
+    pub outcome: Outcome,
+}
+
+// A letter in the guide that the rules don't give a meaning to
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum LetterError {
+    Opponent(char),
+    Response(char),
+}
+
+impl fmt::Display for LetterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Opponent(c) => write!(f, "'{}' isn't one of the opponent's shapes", c),
+            Self::Response(c) => write!(f, "'{}' isn't something we can play", c),
+        }
+    }
+}
+
+// Which round of the guide couldn't be played, counting from 1
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct RoundError {
+    pub round: usize,
+    pub error: LetterError,
+}
+
+impl fmt::Display for RoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "round {}: {}", self.round, self.error)
+    }
+}
+
+// Letters that can't be read as a game's shapes or the outcomes
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum RulesError {
+    WrongLetterCount { letters: usize, expected: usize },
+    DuplicateLetter(char),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLetterCount { letters, expected } => write!(f, "{} letters given, there should be {}", letters, expected),
+            Self::DuplicateLetter(c) => write!(f, "'{}' is given more than one meaning", c),
+        }
+    }
+}
+
+// A game, and what the letters in the strategy guide mean
+#[derive(PartialEq, Clone, Debug)]
+pub struct Rules {
+    pub game: Game,
+    pub opponent: HashMap<char, ShapeId>,
+    pub response: HashMap<char, Response>,
+}
+
+impl Rules {
+    // The letters name the game's shapes in order, in both columns
+    pub fn with_shapes(game: Game, opponent_letters: &str, player_letters: &str) -> Result<Self, RulesError> {
+        let opponent = Self::shape_letters(&game, opponent_letters)?;
+        let response = Self::shape_letters(&game, player_letters)?.into_iter().map(|(c, s)| (c, Response::Shape(s))).collect();
+        Ok(Self { game, opponent, response })
+    }
+
+    // The opponent's letters name the shapes, ours say whether to lose, draw or win
+    pub fn with_outcomes(game: Game, opponent_letters: &str, outcome_letters: [char; 3]) -> Result<Self, RulesError> {
+        let opponent = Self::shape_letters(&game, opponent_letters)?;
+        let outcomes = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+        let response = Self::letters(outcome_letters.into_iter().zip(outcomes.map(Response::Outcome)))?;
+        Ok(Self { game, opponent, response })
+    }
+
+    // A letter for each of the game's shapes, in order
+    fn shape_letters(game: &Game, letters: &str) -> Result<HashMap<char, ShapeId>, RulesError> {
+        let count = letters.chars().count();
+        if count != game.shapes.len() {
+            return Err(RulesError::WrongLetterCount { letters: count, expected: game.shapes.len() });
+        }
+        Self::letters(letters.chars().zip(0..))
+    }
+
+    fn letters<T>(meanings: impl Iterator<Item = (char, T)>) -> Result<HashMap<char, T>, RulesError> {
+        let mut letters = HashMap::new();
+        for (c, meaning) in meanings {
+            if letters.insert(c, meaning).is_some() {
+                return Err(RulesError::DuplicateLetter(c));
+            }
+        }
+        Ok(letters)
+    }
+
+    // X, Y and Z are the shapes we should play
+    pub fn part_one() -> Self {
+        Self::with_shapes(Game::rock_paper_scissors(), "ABC", "XYZ").unwrap()
+    }
+
+    // X, Y and Z say we should lose, draw or win
+    pub fn part_two() -> Self {
+        Self::with_outcomes(Game::rock_paper_scissors(), "ABC", ['X', 'Y', 'Z']).unwrap()
+    }
+
+    pub fn play(&self, opponent: char, response: char) -> Result<Round, LetterError> {
+        let opponent = *self.opponent.get(&opponent).ok_or(LetterError::Opponent(opponent))?;
+        let player = match self.response.get(&response) {
+            Some(Response::Shape(s)) => *s,
+            Some(Response::Outcome(o)) => self.game.shape_for(opponent, *o),
+            None => return Err(LetterError::Response(response)),
+        };
+        Ok(Round { opponent, player, outcome: self.game.outcome(opponent, player) })
+    }
+
+    // Every round of the guide, or the first one with a letter the rules don't know
+    pub fn play_guide(&self, guide: &[(char, char)]) -> Result<Vec<Round>, RoundError> {
+        guide
+            .iter()
+            .enumerate()
+            .map(|(idx, (o, r))| self.play(*o, *r).map_err(|error| RoundError { round: idx + 1, error }))
+            .collect()
+    }
+
+    pub fn score(&self, round: &Round) -> u32 {
+        self.game.score(round.opponent, round.player)
+    }
+
+    // The score from following the whole guide
+    pub fn total_score(&self, guide: &[(char, char)]) -> Result<u32, RoundError> {
+        Ok(self.play_guide(guide)?.iter().map(|round| self.score(round)).sum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GUIDE: [(char, char); 3] = [('A', 'Y'), ('B', 'X'), ('C', 'Z')];
+
+    #[test]
+    fn test_rock_paper_scissors() {
+        let game = Game::rock_paper_scissors();
+        assert_eq!(game.outcome(0, 1), Outcome::Win);
+        assert_eq!(game.outcome(0, 2), Outcome::Loss);
+        assert_eq!(game.outcome(2, 0), Outcome::Win);
+        assert_eq!(game.outcome(1, 1), Outcome::Draw);
+        assert_eq!(game.score(0, 1), 8);
+        assert_eq!(game.shape_for(2, Outcome::Win), 0);
+        assert_eq!(game.shape_for(0, Outcome::Loss), 2);
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(Rules::part_one().total_score(&GUIDE), Ok(15));
+        assert_eq!(Rules::part_two().total_score(&GUIDE), Ok(12));
+        assert_eq!(Rules::part_one().play('A', 'W'), Err(LetterError::Response('W')));
+
+        let error = Rules::part_one().total_score(&[('A', 'X'), ('D', 'X')]).unwrap_err();
+        assert_eq!(error, RoundError { round: 2, error: LetterError::Opponent('D') });
+        assert_eq!(error.to_string(), "round 2: 'D' isn't one of the opponent's shapes");
+    }
+
+    #[test]
+    fn test_bad_letters() {
+        let game = Game::rock_paper_scissors;
+        assert_eq!(Rules::with_shapes(game(), "AB", "XYZ"), Err(RulesError::WrongLetterCount { letters: 2, expected: 3 }));
+        assert_eq!(Rules::with_shapes(game(), "ABC", "WXYZ"), Err(RulesError::WrongLetterCount { letters: 4, expected: 3 }));
+        assert_eq!(Rules::with_shapes(game(), "ABC", "XYX"), Err(RulesError::DuplicateLetter('X')));
+        assert_eq!(Rules::with_outcomes(game(), "AAC", ['X', 'Y', 'Z']), Err(RulesError::DuplicateLetter('A')));
+        let error = Rules::with_outcomes(game(), "ABC", ['X', 'Z', 'Z']).unwrap_err();
+        assert_eq!(error, RulesError::DuplicateLetter('Z'));
+        assert_eq!(error.to_string(), "'Z' is given more than one meaning");
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        // Each beats the two before it, Spock smashes Rock and Rock crushes Scissors and Lizard.
+        // The puzzle doesn't need it, but the rules aren't just for three shapes.
+        let shapes = vec![
+            Shape { name: "Rock", value: 1 },
+            Shape { name: "Spock", value: 5 },
+            Shape { name: "Paper", value: 2 },
+            Shape { name: "Lizard", value: 4 },
+            Shape { name: "Scissors", value: 3 },
+        ];
+        let game = Game::new(shapes, Points { win: 6, draw: 3, loss: 0 }).unwrap();
+        let id = |name: &str| game.shapes.iter().position(|s| s.name == name).unwrap();
+        let beats = [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ];
+        for (winner, loser) in beats {
+            assert_eq!(game.outcome(id(loser), id(winner)), Outcome::Win, "{} beats {}", winner, loser);
+            assert_eq!(game.outcome(id(winner), id(loser)), Outcome::Loss);
+        }
+
+        let rules = Rules::with_outcomes(game.clone(), "ABCDE", ['X', 'Y', 'Z']).unwrap();
+        for opponent in "ABCDE".chars() {
+            assert_eq!(rules.play(opponent, 'Z').unwrap().outcome, Outcome::Win);
+            assert_eq!(rules.play(opponent, 'X').unwrap().outcome, Outcome::Loss);
+        }
+    }
+
+    #[test]
+    fn test_even_games_are_rejected() {
+        let shapes = vec![Shape { name: "Heads", value: 1 }, Shape { name: "Tails", value: 2 }];
+        assert!(Game::new(shapes, Points { win: 1, draw: 0, loss: 0 }).is_err());
+    }
+}
@@ -1,131 +1,138 @@
-#[derive(PartialEq, Clone, Copy, Debug)]
-enum Play {
-    Rock,
-    Paper,
-    Scissors,
-}
+use crate::diagnostic::{Diagnostic, ParseError};
 
-impl Play {
-    fn get_value(&self) -> u32 {
-        match self {
-            Play::Rock => 1,
-            Play::Paper => 2,
-            Play::Scissors => 3,
-        }
-    }
+use super::rules::Rules;
 
-    fn loses_to(&self) -> Self {
-        match self {
-            Play::Rock => Play::Paper,
-            Play::Paper => Play::Scissors,
-            Play::Scissors => Play::Rock,
-        }
-    }
+pub const INPUT: &str = include_str!("input.txt");
 
-    fn wins_to(&self) -> Self {
-        match self {
-            Play::Rock => Play::Scissors,
-            Play::Paper => Play::Rock,
-            Play::Scissors => Play::Paper,
-        }
-    }
+pub fn solution() {
+    println!("Solution for day two part one: {}", part1(INPUT));
+    println!("Solution for day two part two: {}", part2(INPUT));
 }
 
-impl TryFrom<char> for Play {
-    type Error = &'static str;
-
-    fn try_from(c: char) -> Result<Self, Self::Error> {
-        match c {
-            'A' | 'X' => Ok(Play::Rock),
-            'B' | 'Y' => Ok(Play::Paper),
-            'C' | 'Z' => Ok(Play::Scissors),
-            _ => Err("Could not parse input"),
-        }
-    }
+pub fn part1(input: &str) -> u32 {
+    score(&Rules::part_one(), input)
 }
 
-pub fn solution() {
-    println!("Solution for day two part one: {}", part1());
-    println!("Solution for day two part two: {}", part2());
+pub fn part2(input: &str) -> u32 {
+    score(&Rules::part_two(), input)
 }
 
-fn part1() -> u32 {
-    parse_input().iter().map(|(o,p)| calculate_points(o, p)).sum()
+// The guide has been checked against the rules, so every round can be played
+fn score(rules: &Rules, input: &str) -> u32 {
+    rules.total_score(&get_guide(input, rules)).unwrap_or_else(|e| panic!("{}", e))
 }
 
-fn part2() -> u32 {
-    parse_input().iter()
-        .map(|(o,p)| (o, fix_play(o,p)))
-        .map(|(o,p)| calculate_points(o, &p))
-        .sum()
+pub fn get_guide(input: &str, rules: &Rules) -> Vec<(char, char)> {
+    parse_input(input, rules).unwrap_or_else(|e| panic!("{}", e))
 }
 
-fn calculate_points(opponent: &Play, player: &Play) -> u32 {
-    if opponent == player {
-        player.get_value() + 3
-    } else {
-        let points = match (opponent, player) {
-            (Play::Rock, Play::Paper) => 6,
-            (Play::Rock, Play::Scissors) => 0,
-            (Play::Paper, Play::Rock) => 0,
-            (Play::Paper, Play::Scissors) => 6,
-            (Play::Scissors, Play::Rock) =>  6,
-            (Play::Scissors, Play::Paper) => 0,
-            _ => 0
-        };
-        points + player.get_value()
-    }
-}
-
-fn fix_play(opponent: &Play, player: &Play) -> Play {
-    match player {
-        Play::Rock => opponent.wins_to(),       // lose
-        Play::Paper => *opponent,               // draw
-        Play::Scissors => opponent.loses_to(),  // win
+// The strategy guide's letters, each one checked against the letters the rules know
+pub fn parse_input(input: &str, rules: &Rules) -> Result<Vec<(char, char)>, Diagnostic> {
+    let mut guide = Vec::new();
+    let mut offset = 0;
+    for line in input.split('\n') {
+        if !line.trim().is_empty() {
+            let error = |column: usize, expected: Vec<String>| {
+                let error = ParseError { input: &input[offset + column..], expected, contexts: vec!["parse_line"] };
+                Diagnostic::new(input, error)
+            };
+            let [(o_idx, o), (r_idx, r)] = parse_line(line)
+                .map_err(|column| error(column, vec!["a line like \"A Y\", two letters with a space between".to_string()]))?;
+            if !rules.opponent.contains_key(&o) {
+                return Err(error(o_idx, letters(rules.opponent.keys())));
+            }
+            if !rules.response.contains_key(&r) {
+                return Err(error(r_idx, letters(rules.response.keys())));
+            }
+            guide.push((o, r));
+        }
+        offset += line.len() + 1;
     }
+    Ok(guide)
 }
 
-fn parse_input() -> Vec<(Play, Play)> {
-    let input: &str = include_str!("input.txt");
-
-    input
-        .split('\n')
-        .filter(|s| !s.is_empty())
-        .map(parse_line)
-        .map(|r| r.unwrap())
-        .collect()
+fn letters<'a>(letters: impl Iterator<Item = &'a char>) -> Vec<String> {
+    let mut letters: Vec<&char> = letters.collect();
+    letters.sort();
+    letters.iter().map(|c| format!("\"{}\"", c)).collect()
 }
 
-fn parse_line(line: &str) -> Result<(Play, Play), &'static str> {
-    let chars: Vec<char> = line.replace(' ', "").trim().chars().collect();
-    assert!(chars.len() == 2);
-    Ok(
-        ( Play::try_from(chars[0])?, Play::try_from(chars[1])?)
-    )
+// The two letters and where they are, or the byte in the line where it stops making sense
+fn parse_line(line: &str) -> Result<[(usize, char); 2], usize> {
+    let mut letters = line.char_indices().filter(|(_, c)| !c.is_whitespace());
+    let mut next_letter = || match letters.next() {
+        Some((idx, c)) if c.is_ascii_alphabetic() => Ok((idx, c)),
+        Some((idx, _)) => Err(idx),
+        None => Err(line.trim_end().len()),
+    };
+    let first = next_letter()?;
+    let second = next_letter()?;
+    // The letters stand alone, "AY" or "AB Y" aren't guide lines
+    if second.0 == first.0 + 1 {
+        return Err(second.0);
+    }
+    if let Some((idx, _)) = letters.next() {
+        return Err(idx);
+    }
+    Ok([first, second])
 }
 
 #[cfg(test)]
 mod test {
-    use super::Play;
+    use super::Rules;
 
-    fn get_data() -> Vec<(Play, Play)> {
+    fn get_data() -> Vec<(char, char)> {
         vec![
-            (Play::Rock, Play::Paper),
-            (Play::Paper, Play::Rock),
-            (Play::Scissors, Play::Scissors),
+            ('A', 'Y'),
+            ('B', 'X'),
+            ('C', 'Z'),
         ]
     }
 
     #[test]
     fn part1() {
-        assert_eq!(get_data().iter().map(|(o,p)| super::calculate_points(o, p)).sum::<u32>(), 15);
+        assert_eq!(Rules::part_one().total_score(&get_data()), Ok(15));
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let rules = Rules::part_one();
+        assert_eq!(super::parse_input("A Y\nB X\r\n\nC Z\n", &rules).unwrap(), get_data());
+
+        let err = super::parse_input("A Y\nB  X Z\nC Z", &rules).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.parsers, vec!["parse_line"]);
+        let err = super::parse_input("A Y\nB", &rules).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = super::parse_input("AY", &rules).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+        let err = super::parse_input("A 3", &rules).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+    }
+
+    #[test]
+    fn test_unknown_letters() {
+        let rules = Rules::part_one();
+        let err = super::parse_input("A Y\nD Q\n", &rules).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, vec!["\"A\"", "\"B\"", "\"C\""]);
+        let err = super::parse_input("A Y\nB  Q\n", &rules).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert!(err.to_string().starts_with("error: parse_line failed, expected one of \"X\", \"Y\" or \"Z\""));
     }
 
     #[test]
     fn part2() {
-        let fixed: Vec<(Play, Play)> = get_data().iter().map(|(o,p)| (*o, super::fix_play(o, p))).collect();
+        let rules = Rules::part_two();
+        let fixed: Vec<usize> = get_data().iter().map(|(o,p)| rules.play(*o, *p).unwrap().player).collect();
         println!("fixed = {:?}", fixed);
-        assert_eq!(fixed.iter().map(|(o,p)| super::calculate_points(o, p)).sum::<u32>(), 12);
+        assert_eq!(fixed, vec![0, 0, 0]);
+        assert_eq!(rules.total_score(&get_data()), Ok(12));
     }
 
+    #[test]
+    fn test_answers() {
+        assert_eq!(super::part1(super::INPUT), 13009);
+        assert_eq!(super::part2(super::INPUT), 10398);
+    }
 }
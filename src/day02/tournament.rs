@@ -0,0 +1,256 @@
+use std::fmt;
+
+use super::rules::{Game, Outcome, Rules, ShapeId};
+use crate::rng::Rng;
+
+// One round, as a player saw it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Seen {
+    pub mine: ShapeId,
+    pub theirs: ShapeId,
+}
+
+pub trait Strategy {
+    fn name(&self) -> String;
+    // Pick a shape for the next round, knowing how the match has gone so far
+    fn choose(&mut self, game: &Game, history: &[Seen]) -> ShapeId;
+}
+
+// Plays our shapes from a strategy guide, one after another, starting again at the end
+pub struct FixedGuide {
+    name: String,
+    shapes: Vec<ShapeId>,
+}
+
+impl FixedGuide {
+    // The shapes the rules say to play, for each line of the guide
+    pub fn new(name: &str, rules: &Rules, guide: &[(char, char)]) -> Result<Self, String> {
+        let shapes: Vec<ShapeId> = rules.play_guide(guide).map_err(|e| e.to_string())?.iter().map(|round| round.player).collect();
+        if shapes.is_empty() {
+            return Err("The guide is empty".to_string());
+        }
+        Ok(Self { name: name.to_string(), shapes })
+    }
+}
+
+impl Strategy for FixedGuide {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn choose(&mut self, _: &Game, history: &[Seen]) -> ShapeId {
+        self.shapes[history.len() % self.shapes.len()]
+    }
+}
+
+pub struct Random {
+    seed: u64,
+    rng: Rng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, rng: Rng::new(seed) }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("random (seed {})", self.seed)
+    }
+
+    fn choose(&mut self, game: &Game, _: &[Seen]) -> ShapeId {
+        self.rng.below(game.shapes.len())
+    }
+}
+
+// Beats whatever the opponent has played the most so far
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    fn choose(&mut self, game: &Game, history: &[Seen]) -> ShapeId {
+        let mut counts = vec![0; game.shapes.len()];
+        history.iter().for_each(|s| counts[s.theirs] += 1);
+        game.shape_for(most_likely(&counts), Outcome::Win)
+    }
+}
+
+// Predicts the opponent's next shape from what they've played after their last one before
+pub struct Markov;
+
+impl Strategy for Markov {
+    fn name(&self) -> String {
+        "markov".to_string()
+    }
+
+    fn choose(&mut self, game: &Game, history: &[Seen]) -> ShapeId {
+        let Some(last) = history.last() else {
+            return 0;
+        };
+        let mut counts = vec![0; game.shapes.len()];
+        history.windows(2).filter(|w| w[0].theirs == last.theirs).for_each(|w| counts[w[1].theirs] += 1);
+        game.shape_for(most_likely(&counts), Outcome::Win)
+    }
+}
+
+// The most counted shape, the first of them when there's a tie
+fn most_likely(counts: &[u32]) -> ShapeId {
+    counts.iter().enumerate().rev().max_by_key(|(_, c)| **c).map(|(s, _)| s).unwrap_or(0)
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub points: u32,
+}
+
+impl Standing {
+    pub fn win_rate(&self) -> f64 {
+        let played = self.wins + self.draws + self.losses;
+        if played == 0 {
+            0.0
+        } else {
+            self.wins as f64 / played as f64
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Leaderboard {
+    // Best win rate first, then most points
+    pub standings: Vec<Standing>,
+}
+
+// Every strategy plays every other for the given number of rounds, scored by the game
+pub fn play_tournament(game: &Game, strategies: &mut [Box<dyn Strategy>], rounds: usize) -> Leaderboard {
+    let mut standings: Vec<Standing> = strategies.iter().map(|s| Standing { name: s.name(), ..Default::default() }).collect();
+    for a in 0..strategies.len() {
+        for b in a + 1..strategies.len() {
+            let (first, second) = strategies.split_at_mut(b);
+            let (seen_a, seen_b) = play_match(game, first[a].as_mut(), second[0].as_mut(), rounds);
+            record(game, &mut standings[a], &seen_a);
+            record(game, &mut standings[b], &seen_b);
+        }
+    }
+    standings.sort_by(|x, y| y.win_rate().total_cmp(&x.win_rate()).then(y.points.cmp(&x.points)));
+    Leaderboard { standings }
+}
+
+// The match as each player saw it
+fn play_match(game: &Game, a: &mut dyn Strategy, b: &mut dyn Strategy, rounds: usize) -> (Vec<Seen>, Vec<Seen>) {
+    let mut seen_a = Vec::with_capacity(rounds);
+    let mut seen_b = Vec::with_capacity(rounds);
+    for _ in 0..rounds {
+        let shape_a = a.choose(game, &seen_a);
+        let shape_b = b.choose(game, &seen_b);
+        seen_a.push(Seen { mine: shape_a, theirs: shape_b });
+        seen_b.push(Seen { mine: shape_b, theirs: shape_a });
+    }
+    (seen_a, seen_b)
+}
+
+fn record(game: &Game, standing: &mut Standing, seen: &[Seen]) {
+    for s in seen {
+        match game.outcome(s.theirs, s.mine) {
+            Outcome::Win => standing.wins += 1,
+            Outcome::Draw => standing.draws += 1,
+            Outcome::Loss => standing.losses += 1,
+        }
+        standing.points += game.score(s.theirs, s.mine);
+    }
+}
+
+// A made up strategy guide, in the puzzle's letters
+pub fn generate_guide(seed: u64, rounds: usize) -> Vec<(char, char)> {
+    let mut rng = Rng::new(seed);
+    (0..rounds)
+        .map(|_| (['A', 'B', 'C'][rng.below(3)], ['X', 'Y', 'Z'][rng.below(3)]))
+        .collect()
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.standings.iter().map(|s| s.name.len()).max().unwrap_or(0).max(8);
+        writeln!(f, "{:<w$}  {:>6}  {:>6}  {:>6}  {:>8}  {:>8}", "Strategy", "Wins", "Draws", "Losses", "Points", "Win rate", w = width)?;
+        for s in &self.standings {
+            writeln!(
+                f,
+                "{:<w$}  {:>6}  {:>6}  {:>6}  {:>8}  {:>7.1}%",
+                s.name,
+                s.wins,
+                s.draws,
+                s.losses,
+                s.points,
+                s.win_rate() * 100.0,
+                w = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GUIDE: [(char, char); 3] = [('A', 'Y'), ('B', 'X'), ('C', 'Z')];
+
+    #[test]
+    fn test_fixed_guide() {
+        let game = Game::rock_paper_scissors();
+        let mut guide = FixedGuide::new("guide", &Rules::part_two(), &GUIDE).unwrap();
+        let history = [Seen { mine: 0, theirs: 0 }; 4];
+        // Part two's reading of the guide plays Rock every time
+        assert_eq!((0..=4).map(|n| guide.choose(&game, &history[..n])).collect::<Vec<_>>(), vec![0; 5]);
+
+        let mut guide = FixedGuide::new("guide", &Rules::part_one(), &GUIDE).unwrap();
+        assert_eq!(guide.choose(&game, &history[..3]), 1);
+        assert!(FixedGuide::new("empty", &Rules::part_one(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_predictors() {
+        let game = Game::rock_paper_scissors();
+        let seen = |theirs: &[ShapeId]| theirs.iter().map(|t| Seen { mine: 0, theirs: *t }).collect::<Vec<Seen>>();
+        // Mostly Scissors, so Rock
+        assert_eq!(FrequencyCounter.choose(&game, &seen(&[2, 2, 1])), 0);
+        // After Rock they've always played Paper, so Scissors
+        assert_eq!(Markov.choose(&game, &seen(&[0, 1, 2, 0, 1, 2, 0])), 2);
+        assert_eq!(Markov.choose(&game, &[]), 0);
+    }
+
+    #[test]
+    fn test_tournament() {
+        let game = Game::rock_paper_scissors();
+        let rock = FixedGuide::new("rock", &Rules::part_one(), &[('A', 'X')]).unwrap();
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![Box::new(rock), Box::new(FrequencyCounter), Box::new(Markov)];
+        let leaderboard = play_tournament(&game, &mut strategies, 100);
+
+        // Everything learns to beat Rock after the first round
+        let rock = &leaderboard.standings[2];
+        assert_eq!(rock.name, "rock");
+        assert_eq!((rock.wins, rock.draws, rock.losses), (0, 1, 199));
+        let total: u32 = leaderboard.standings.iter().map(|s| s.wins + s.draws + s.losses).sum();
+        assert_eq!(total, 600);
+    }
+
+    #[test]
+    fn test_seeded_runs_repeat() {
+        assert_eq!(generate_guide(7, 50), generate_guide(7, 50));
+        assert_ne!(generate_guide(7, 50), generate_guide(8, 50));
+
+        let game = Game::rock_paper_scissors();
+        let run = || {
+            let mut strategies: Vec<Box<dyn Strategy>> = vec![Box::new(Random::new(1)), Box::new(Random::new(2)), Box::new(Markov)];
+            play_tournament(&game, &mut strategies, 500)
+        };
+        assert_eq!(run(), run());
+    }
+}
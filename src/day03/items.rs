@@ -0,0 +1,79 @@
+// A set of item priorities (1 to 52) as the bits of a u64, bit n is set for priority n
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ItemMask(u64);
+
+// A set type, so not all of it is needed by the puzzle
+#[allow(dead_code)]
+impl ItemMask {
+    // Every item type, a to z and A to Z
+    pub const ALL: ItemMask = ItemMask(((1 << 52) - 1) << 1);
+
+    pub fn from_priorities(priorities: impl IntoIterator<Item = u32>) -> Self {
+        priorities.into_iter().fold(Self::default(), |mask, p| mask.with(p))
+    }
+
+    pub fn with(self, priority: u32) -> Self {
+        debug_assert!((1..=52).contains(&priority));
+        Self(self.0 | 1 << priority)
+    }
+
+    pub fn contains(&self, priority: u32) -> bool {
+        self.0 & 1 << priority != 0
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // The priorities, lowest first
+    pub fn iter(&self) -> Priorities {
+        Priorities(self.0)
+    }
+}
+
+pub struct Priorities(u64);
+
+impl Iterator for Priorities {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            return None;
+        }
+        let priority = self.0.trailing_zeros();
+        // Clear the lowest bit
+        self.0 &= self.0 - 1;
+        Some(priority)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_item_mask() {
+        let a = ItemMask::from_priorities([1, 16, 52]);
+        let b = ItemMask::from_priorities([16, 27, 52]);
+        assert_eq!(a.intersection(b).iter().collect::<Vec<u32>>(), vec![16, 52]);
+        assert_eq!(a.union(b).iter().collect::<Vec<u32>>(), vec![1, 16, 27, 52]);
+        assert_eq!(a.union(b).len(), 4);
+        assert!(a.contains(1) && !a.contains(2));
+        assert!(a.intersection(ItemMask::default()).is_empty());
+        assert_eq!(ItemMask::ALL.len(), 52);
+        assert_eq!(ItemMask::ALL.iter().next(), Some(1));
+        assert_eq!(ItemMask::ALL.iter().last(), Some(52));
+    }
+}
@@ -1 +1,3 @@
+pub mod items;
+pub mod reorganise;
 pub mod solution;
@@ -0,0 +1,478 @@
+use priority_queue::PriorityQueue;
+use std::{collections::HashMap, fmt};
+
+use super::{
+    items::ItemMask,
+    solution::{only_item, rucksacks, str_to_mask, to_value, RucksackError},
+};
+
+#[derive(Debug, PartialEq)]
+pub enum Swap {
+    // The left item moves to the right compartment, and the right item to the left
+    Compartments { line: usize, left: char, right: char },
+    // Two rucksacks in a group trade an item
+    Rucksacks { line: usize, gives: char, other_line: usize, takes: char },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Reorganisation {
+    pub swaps: Vec<Swap>,
+    // The rucksacks afterwards, in the order of the input
+    pub rucksacks: Vec<String>,
+}
+
+// How many arrangements of a group's items to look at before giving up
+const MAX_STATES: usize = 100_000;
+
+// Rucksack `from` gives an item for one of rucksack `to`'s, each is an item type and
+// whether it's in the left compartment
+#[derive(Clone, Copy, Debug)]
+struct Trade {
+    from: usize,
+    gives: (u8, bool),
+    to: usize,
+    takes: (u8, bool),
+}
+
+// How to split a rucksack's item types between its compartments
+struct Split {
+    // The item types that go in the left compartment, the rest go right
+    left: ItemMask,
+    swaps: usize,
+}
+
+// Plan the fewest swaps so that no item type is in both compartments of a rucksack,
+// without changing any group's badge. A compartment swap can always wait until after the
+// trades: trading an item from where it was, and swapping the one that comes in instead,
+// moves the same items. So a group's plan is some trades, found by cheapest, and then the
+// fewest compartment swaps for what each rucksack holds, found by split.
+pub fn plan(input: &str, group_size: usize) -> Result<Reorganisation, RucksackError> {
+    if group_size == 0 {
+        return Err(RucksackError::EmptyGroups);
+    }
+    let all: Vec<(usize, &str)> = rucksacks(input).collect();
+    let mut swaps = Vec::new();
+    let mut result = Vec::new();
+    for group in all.chunks(group_size) {
+        let (first_line, _) = group[0];
+        if group.len() < group_size {
+            return Err(RucksackError::IncompleteGroup { line: first_line, rucksacks: group.len(), group_size });
+        }
+        for (line, items) in group {
+            str_to_mask(*line, items)?;
+            if !items.len().is_multiple_of(2) {
+                return Err(RucksackError::UnevenCompartments { line: *line, items: items.len(), compartments: 2 });
+            }
+        }
+        let badge = only_item(first_line, common(group.iter().map(|(_, items)| items.as_bytes())))?;
+
+        let mut contents: Vec<Vec<u8>> = group.iter().map(|(_, items)| items.as_bytes().to_vec()).collect();
+        for trade in cheapest(group, &contents, badge)? {
+            swaps.push(apply(group, &mut contents, &trade));
+        }
+        for (r, (line, _)) in group.iter().enumerate() {
+            let split = split(&contents[r]).expect("Every rucksack can be split after the trades");
+            swaps.extend(rearrange(*line, &mut contents[r], split.left));
+            result.push(String::from_utf8(contents[r].clone()).unwrap());
+        }
+    }
+    Ok(Reorganisation { swaps, rucksacks: result })
+}
+
+// The item types every rucksack has
+fn common<'a>(rucksacks: impl Iterator<Item = &'a [u8]>) -> ItemMask {
+    rucksacks.fold(ItemMask::ALL, |common, items| common.intersection(mask(items)))
+}
+
+fn mask(items: &[u8]) -> ItemMask {
+    ItemMask::from_priorities(items.iter().filter_map(|c| to_value(*c as char)))
+}
+
+// Each item type goes wholly to one side, and the left side has to end up half full.
+// A type on the left costs the copies that were on the right, and the other way round.
+// It's a subset sum over the types, keeping the cheapest way to fill each size.
+fn split(items: &[u8]) -> Option<Split> {
+    let half = items.len() / 2;
+    let mut counts = [(0, 0); 53];
+    for (idx, c) in items.iter().enumerate() {
+        let p = to_value(*c as char).unwrap() as usize;
+        if idx < half {
+            counts[p].0 += 1;
+        } else {
+            counts[p].1 += 1;
+        }
+    }
+
+    let mut best: Vec<Option<(usize, ItemMask)>> = vec![None; half + 1];
+    best[0] = Some((0, ItemMask::default()));
+    for p in mask(items).iter() {
+        let (left, right) = counts[p as usize];
+        let mut next: Vec<Option<(usize, ItemMask)>> = vec![None; half + 1];
+        for (size, option) in best.iter().enumerate() {
+            let Some((cost, mask)) = *option else {
+                continue;
+            };
+            let mut offer = |size: usize, option: (usize, ItemMask)| {
+                if next[size].is_none_or(|(c, _)| option.0 < c) {
+                    next[size] = Some(option);
+                }
+            };
+            offer(size, (cost + left, mask));
+            if size + left + right <= half {
+                offer(size + left + right, (cost + right, mask.with(p)));
+            }
+        }
+        best = next;
+    }
+    // Every item that crosses over is matched by one going the other way
+    best[half].map(|(cost, left)| Split { left, swaps: cost / 2 })
+}
+
+// Swap items over in place, pairing each item on the wrong side with one on the other wrong side
+fn rearrange(line: usize, items: &mut [u8], left: ItemMask) -> Vec<Swap> {
+    let half = items.len() / 2;
+    let goes_left = |c: u8| left.contains(to_value(c as char).unwrap());
+    let to_right: Vec<usize> = (0..half).filter(|i| !goes_left(items[*i])).collect();
+    let to_left: Vec<usize> = (half..items.len()).filter(|i| goes_left(items[*i])).collect();
+    to_right
+        .into_iter()
+        .zip(to_left)
+        .map(|(l, r)| {
+            items.swap(l, r);
+            Swap::Compartments { line, left: items[r] as char, right: items[l] as char }
+        })
+        .collect()
+}
+
+// The trades that leave the fewest swaps in all, by A* over what the group holds after
+// each trade. A trade changes one item in each of two rucksacks and a compartment swap
+// moves two in one, so neither does away with more than two of the items that share a
+// type with items on the other side, see estimate. The greedy trades are the plan to
+// beat, nothing that can't do better than them is looked at.
+fn cheapest(group: &[(usize, &str)], start: &[Vec<u8>], badge: u32) -> Result<Vec<Trade>, RucksackError> {
+    // The cheapest plan so far, as its swaps in all and its trades
+    let mut best: Option<(usize, Vec<Trade>)> = greedy(group, start, badge).and_then(|trades| {
+        let mut contents = start.to_vec();
+        trades.iter().for_each(|t| {
+            apply(group, &mut contents, t);
+        });
+        finish(&contents, badge).map(|swaps| (trades.len() + swaps, trades))
+    });
+
+    // Arrangements are numbered as they're found
+    let mut states: Vec<Vec<Vec<u8>>> = vec![start.to_vec()];
+    let mut ids: HashMap<Vec<Vec<u8>>, usize> = HashMap::from([(key(start), 0)]);
+    let mut came_from: HashMap<usize, (usize, Trade)> = HashMap::new();
+    let mut cost_so_far: HashMap<usize, usize> = HashMap::from([(0, 0)]);
+    let mut frontier = PriorityQueue::new();
+    frontier.push(0, -(estimate(start) as i64));
+
+    while let Some((current, priority)) = frontier.pop() {
+        // Nothing left can do better than the best so far
+        if best.as_ref().is_some_and(|(swaps, _)| -priority >= *swaps as i64) {
+            break;
+        }
+        let trades = cost_so_far[&current];
+        if let Some(swaps) = finish(&states[current], badge) {
+            if best.as_ref().is_none_or(|(b, _)| trades + swaps < *b) {
+                best = Some((trades + swaps, path(&came_from, current)));
+            }
+        }
+        for (next, trade) in moves(group, &states[current]) {
+            let cost = trades + 1;
+            let bound = cost + estimate(&next);
+            if best.as_ref().is_some_and(|(swaps, _)| bound >= *swaps) {
+                continue;
+            }
+            let id = match ids.get(&key(&next)) {
+                Some(id) => *id,
+                None => {
+                    if states.len() == MAX_STATES {
+                        return Err(RucksackError::TooManyStates { line: group[0].0, explored: states.len() });
+                    }
+                    ids.insert(key(&next), states.len());
+                    states.push(next);
+                    states.len() - 1
+                }
+            };
+            if cost_so_far.get(&id).is_none_or(|c| cost < *c) {
+                cost_so_far.insert(id, cost);
+                // Negative, so the lowest bound comes out first
+                frontier.push(id, -(bound as i64));
+                came_from.insert(id, (current, trade));
+            }
+        }
+    }
+    match best {
+        Some((_, trades)) => Ok(trades),
+        None => {
+            let (line, _) = group.iter().zip(start).find(|(_, items)| split(items).is_none()).map_or(group[0], |(r, _)| *r);
+            Err(RucksackError::Unsplittable { line })
+        }
+    }
+}
+
+// The compartment swaps left once the group holds these, if each rucksack can be split
+// and the badge is still the only type they share
+fn finish(contents: &[Vec<u8>], badge: u32) -> Option<usize> {
+    if common(contents.iter().map(|c| c.as_slice())).iter().ne([badge]) {
+        return None;
+    }
+    contents.iter().map(|items| split(items).map(|s| s.swaps)).sum()
+}
+
+// Never more than the swaps left, see cheapest. A type with copies on both sides needs
+// the copies on one side or the other to go.
+fn estimate(contents: &[Vec<u8>]) -> usize {
+    let mut misplaced = 0;
+    for items in contents {
+        let mut counts = [(0, 0); 53];
+        let half = items.len() / 2;
+        for (idx, c) in items.iter().enumerate() {
+            let p = to_value(*c as char).unwrap() as usize;
+            if idx < half {
+                counts[p].0 += 1;
+            } else {
+                counts[p].1 += 1;
+            }
+        }
+        misplaced += counts.iter().map(|(left, right)| *left.min(right)).sum::<usize>();
+    }
+    misplaced.div_ceil(2)
+}
+
+// What the group holds, which compartment an item is in matters but not where in it
+fn key(contents: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    contents
+        .iter()
+        .map(|items| {
+            let (left, right) = items.split_at(items.len() / 2);
+            let (mut left, mut right) = (left.to_vec(), right.to_vec());
+            left.sort();
+            right.sort();
+            left.extend(right);
+            left
+        })
+        .collect()
+}
+
+// Every trade between two rucksacks of the group, and what the group holds after it
+fn moves(group: &[(usize, &str)], contents: &[Vec<u8>]) -> Vec<(Vec<Vec<u8>>, Trade)> {
+    let mut moves = Vec::new();
+    for from in 0..contents.len() {
+        for to in from + 1..contents.len() {
+            for gives in placed(&contents[from]) {
+                for takes in placed(&contents[to]).into_iter().filter(|t| t.0 != gives.0) {
+                    let trade = Trade { from, gives, to, takes };
+                    let mut next = contents.to_vec();
+                    apply(group, &mut next, &trade);
+                    moves.push((next, trade));
+                }
+            }
+        }
+    }
+    moves
+}
+
+// Each item type in a rucksack, and whether it's in the left compartment, once for each side it's on
+fn placed(items: &[u8]) -> Vec<(u8, bool)> {
+    let half = items.len() / 2;
+    let mut placed: Vec<(u8, bool)> = items.iter().enumerate().map(|(idx, c)| (*c, idx < half)).collect();
+    placed.sort();
+    placed.dedup();
+    placed
+}
+
+fn path(came_from: &HashMap<usize, (usize, Trade)>, end: usize) -> Vec<Trade> {
+    let mut trades = Vec::new();
+    let mut current = end;
+    while let Some((previous, trade)) = came_from.get(&current) {
+        trades.push(*trade);
+        current = *previous;
+    }
+    trades.reverse();
+    trades
+}
+
+// Make the trade, the items going into the compartments the others came out of
+fn apply(group: &[(usize, &str)], contents: &mut [Vec<u8>], trade: &Trade) -> Swap {
+    let position = |items: &[u8], (item, left): (u8, bool)| {
+        let half = items.len() / 2;
+        items.iter().enumerate().position(|(idx, c)| *c == item && (idx < half) == left).expect("The item is there to trade")
+    };
+    let i = position(&contents[trade.from], trade.gives);
+    let j = position(&contents[trade.to], trade.takes);
+    contents[trade.from][i] = trade.takes.0;
+    contents[trade.to][j] = trade.gives.0;
+    Swap::Rucksacks { line: group[trade.from].0, gives: trade.gives.0 as char, other_line: group[trade.to].0, takes: trade.takes.0 as char }
+}
+
+// Each rucksack that can't be split makes the one trade that leaves the fewest swaps
+// afterwards, None if one can't. Not always the fewest in all, see cheapest.
+fn greedy(group: &[(usize, &str)], start: &[Vec<u8>], badge: u32) -> Option<Vec<Trade>> {
+    let mut contents = start.to_vec();
+    let mut trades = Vec::new();
+    for r in 0..contents.len() {
+        if split(&contents[r]).is_none() {
+            let trade = trade(&contents, r, badge)?;
+            apply(group, &mut contents, &trade);
+            trades.push(trade);
+        }
+    }
+    Some(trades)
+}
+
+// The trade with another rucksack in the group that lets this one be split, keeping the
+// badge as the only item type the whole group shares. The cheapest wins, counting the
+// trade and the compartment swaps it leaves in both rucksacks.
+fn trade(contents: &[Vec<u8>], r: usize, badge: u32) -> Option<Trade> {
+    let swaps = |items: &[u8]| split(items).map(|s| s.swaps);
+    let mut best: Option<(isize, usize, usize, usize)> = None;
+    for other in (0..contents.len()).filter(|o| *o != r) {
+        let Some(other_swaps) = swaps(&contents[other]) else {
+            continue;
+        };
+        for (i, give) in contents[r].iter().enumerate() {
+            for (j, take) in contents[other].iter().enumerate() {
+                if give == take || to_value(*give as char) == Some(badge) || to_value(*take as char) == Some(badge) {
+                    continue;
+                }
+                let (mut mine, mut theirs) = (contents[r].clone(), contents[other].clone());
+                mine[i] = *take;
+                theirs[j] = *give;
+                let traded = contents.iter().enumerate().map(|(k, c)| match k {
+                    k if k == r => mine.as_slice(),
+                    k if k == other => theirs.as_slice(),
+                    _ => c.as_slice(),
+                });
+                if common(traded).iter().ne([badge]) {
+                    continue;
+                }
+                if let (Some(a), Some(b)) = (swaps(&mine), swaps(&theirs)) {
+                    // The other rucksack's swaps were going to happen anyway, only the change counts
+                    let cost = 1 + a as isize + b as isize - other_swaps as isize;
+                    if best.is_none_or(|(c, ..)| cost < c) {
+                        best = Some((cost, other, i, j));
+                    }
+                }
+            }
+        }
+    }
+    let (_, other, i, j) = best?;
+    let place = |items: &[u8], idx: usize| (items[idx], idx < items.len() / 2);
+    Some(Trade { from: r, gives: place(&contents[r], i), to: other, takes: place(&contents[other], j) })
+}
+
+impl fmt::Display for Reorganisation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for swap in &self.swaps {
+            match swap {
+                Swap::Compartments { line, left, right } => {
+                    writeln!(f, "line {}: swap {} from the left compartment with {} from the right", line, left, right)?
+                }
+                Swap::Rucksacks { line, gives, other_line, takes } => {
+                    writeln!(f, "line {}: trade {} for {} from line {}", line, gives, takes, other_line)?
+                }
+            }
+        }
+        writeln!(f, "{} swaps for {} rucksacks", self.swaps.len(), self.rucksacks.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::solution::to_item;
+
+    const TEST_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    // No type in both compartments, and each group's badge is the only type they share
+    fn check(reorganisation: &Reorganisation, badges: &[char]) {
+        for items in &reorganisation.rucksacks {
+            let (left, right) = items.split_at(items.len() / 2);
+            assert!(mask(left.as_bytes()).intersection(mask(right.as_bytes())).is_empty(), "{}", items);
+        }
+        for (group, badge) in reorganisation.rucksacks.chunks(3).zip(badges) {
+            let shared: Vec<char> = common(group.iter().map(|r| r.as_bytes())).iter().map(to_item).collect();
+            assert_eq!(shared, vec![*badge]);
+        }
+    }
+
+    #[test]
+    fn test_split() {
+        // One p on each side, swap the right p for the left's b
+        let s = split(b"apbpcd").unwrap();
+        assert_eq!(s.swaps, 1);
+        // Three a's can't go wholly on either side of a rucksack of four
+        assert!(split(b"aaab").is_none());
+        assert_eq!(split(b"").unwrap().swaps, 0);
+    }
+
+    #[test]
+    fn test_plan() {
+        let reorganisation = plan(TEST_INPUT, 3).unwrap();
+        check(&reorganisation, &['r', 'Z']);
+        // The first rucksack has a p on each side and the second an L, trading one for the
+        // other sorts both out at once
+        assert_eq!(reorganisation.swaps[0], Swap::Rucksacks { line: 1, gives: 'p', other_line: 2, takes: 'L' });
+        assert_eq!(reorganisation.rucksacks[0], "vJrwpWtwJgWrhcsFMMfFFhFL");
+        assert_eq!(reorganisation.swaps.len(), 6);
+    }
+
+    #[test]
+    fn test_fewer_than_greedy() {
+        // Each of the first two rucksacks can be split with a compartment swap, so greedy
+        // makes no trades. Trading the a on the right of one for the x on the right of the
+        // other does both in one.
+        let input = "zabacd\nzxyxwv\nzmnopq";
+        let group: Vec<(usize, &str)> = rucksacks(input).collect();
+        let start: Vec<Vec<u8>> = group.iter().map(|(_, items)| items.as_bytes().to_vec()).collect();
+        let badge = to_value('z').unwrap();
+        assert_eq!(greedy(&group, &start, badge).map(|trades| trades.len()), Some(0));
+        assert_eq!(finish(&start, badge), Some(2));
+
+        let reorganisation = plan(input, 3).unwrap();
+        check(&reorganisation, &['z']);
+        assert_eq!(reorganisation.swaps, vec![Swap::Rucksacks { line: 1, gives: 'a', other_line: 2, takes: 'x' }]);
+        assert_eq!(reorganisation.rucksacks, vec!["zabxcd", "zxyawv", "zmnopq"]);
+    }
+
+    #[test]
+    fn test_several_trades() {
+        // Six a's won't fit in one side of eight, two of them have to go. One trade isn't
+        // enough, so greedy can't find a plan.
+        let input = "aaaaaabc\nbdefghij\nbklmnopq";
+        let group: Vec<(usize, &str)> = rucksacks(input).collect();
+        let start: Vec<Vec<u8>> = group.iter().map(|(_, items)| items.as_bytes().to_vec()).collect();
+        assert!(greedy(&group, &start, to_value('b').unwrap()).is_none());
+
+        let reorganisation = plan(input, 3).unwrap();
+        check(&reorganisation, &['b']);
+        assert_eq!(reorganisation.swaps.len(), 2);
+        assert!(reorganisation.swaps.iter().all(|s| matches!(s, Swap::Rucksacks { line: 1, .. })));
+    }
+
+    #[test]
+    fn test_trade_between_rucksacks() {
+        // aaab can't be split, until it trades an a for the second rucksack's c
+        let reorganisation = plan("aaab\nbccd\nbeef", 3).unwrap();
+        check(&reorganisation, &['b']);
+        assert_eq!(reorganisation.swaps[0], Swap::Rucksacks { line: 1, gives: 'a', other_line: 2, takes: 'c' });
+        assert_eq!(reorganisation.swaps.len(), 1 + 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(plan("abcd\nabcd\nabcd", 3), Err(RucksackError::SeveralCommonItems { line: 1, items: vec!['a', 'b', 'c', 'd'] }));
+        assert_eq!(plan("aaaa\naaaa\naaaa", 3), Err(RucksackError::Unsplittable { line: 1 }));
+        assert_eq!(plan("ab\nab", 3), Err(RucksackError::IncompleteGroup { line: 1, rucksacks: 2, group_size: 3 }));
+        assert_eq!(plan("abc\nab\nab", 3), Err(RucksackError::UnevenCompartments { line: 1, items: 3, compartments: 2 }));
+        assert_eq!(plan("ab\nab", 0), Err(RucksackError::EmptyGroups));
+    }
+}
@@ -1,65 +1,203 @@
-use std::collections::HashSet;
+use std::fmt;
 
-const INPUT: &'static str = include_str!("input.txt");
+use super::items::ItemMask;
+
+pub const INPUT: &str = include_str!("input.txt");
 
 pub fn solution() {
-    println!("Solution for day three part one is {}", part1());
-    println!("Solution for day three part two is {}", part2());
+    println!("Solution for day three part one is {}", part1(INPUT));
+    println!("Solution for day three part two is {}", part2(INPUT));
+}
+
+pub fn part1(input: &str) -> u32 {
+    sum_compartments(input, 2).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn part2(input: &str) -> u32 {
+    sum_groups(input, 3).unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RucksackError {
+    // Only a to z and A to Z are item types
+    InvalidItem { line: usize, item: char },
+    // The rucksack can't be split into compartments of the same size
+    UnevenCompartments { line: usize, items: usize, compartments: usize },
+    // Groups need at least one rucksack in them
+    EmptyGroups,
+    // The input ran out part way through a group
+    IncompleteGroup { line: usize, rucksacks: usize, group_size: usize },
+    // For a group, the line is its first rucksack's
+    NoCommonItem { line: usize },
+    SeveralCommonItems { line: usize, items: Vec<char> },
+    // No way of trading items within the group lets the rucksack be split
+    Unsplittable { line: usize },
+    // Looking for the fewest swaps took too long, the line is the group's first rucksack's
+    TooManyStates { line: usize, explored: usize },
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidItem { line, item } => write!(f, "line {}: {:?} isn't an item type, they're a-z and A-Z", line, item),
+            Self::UnevenCompartments { line, items, compartments } => {
+                write!(f, "line {}: {} items can't be split evenly into {} compartments", line, items, compartments)
+            }
+            Self::EmptyGroups => write!(f, "a group has to have at least one rucksack"),
+            Self::IncompleteGroup { line, rucksacks, group_size } => {
+                write!(f, "line {}: the last group has {} rucksacks, not {}", line, rucksacks, group_size)
+            }
+            Self::NoCommonItem { line } => write!(f, "line {}: there's no item type in common", line),
+            Self::SeveralCommonItems { line, items } => {
+                write!(f, "line {}: there's more than one item type in common, {}", line, items.iter().collect::<String>())
+            }
+            Self::Unsplittable { line } => {
+                write!(f, "line {}: the item types can't be kept to one compartment each, even trading within the group", line)
+            }
+            Self::TooManyStates { line, explored } => {
+                write!(f, "line {}: gave up looking for the fewest swaps after {} arrangements", line, explored)
+            }
+        }
+    }
 }
 
-fn part1() -> u32 {
-    INPUT.split('\n')
-        .map(|l| line_to_hashsets_with_values(l))
-        .map(|(x,y)| {
-            let v = x.intersection(&y).next().clone();
-            v.map(|x| *x)
-        })
-        .filter(|n| n.is_some())
-        .map(|n| n.unwrap())
-        .sum()
+// The rucksacks with their line numbers, skipping blank lines
+pub fn rucksacks(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.split('\n').enumerate().map(|(idx, l)| (idx + 1, l.trim_end())).filter(|(_, l)| !l.is_empty())
 }
 
-fn part2() -> u32 {
-    let lines: Vec<&str> = INPUT.split('\n').filter(|s| !s.is_empty()).collect();
-    lines
-        .as_slice()
-        .chunks(3)
-        .map(|window| {
-            let three_lines: Vec<&str> = window.iter().map(|s| *s).collect();
-            lines_to_common_value(&three_lines)
-        }).sum()
+// Sum the priority of the item type found in every compartment of each rucksack
+pub fn sum_compartments(input: &str, compartments: usize) -> Result<u32, RucksackError> {
+    rucksacks(input).map(|(line, items)| common_in_compartments(line, items, compartments)).sum()
 }
 
-fn line_to_hashsets_with_values(line: &str) -> (HashSet<u32>, HashSet<u32>) {
-        let (first, second) = line.split_at((line.len()/2) as usize);
-        let x: HashSet<u32> = str_to_value_set(first);
-        let y: HashSet<u32> = str_to_value_set(second);
-        (x,y)
+// Sum the priority of the badge, the item type every rucksack in a group has. Nothing
+// here allocates, the groups are gathered as the lines go past.
+pub fn sum_groups(input: &str, group_size: usize) -> Result<u32, RucksackError> {
+    if group_size == 0 {
+        return Err(RucksackError::EmptyGroups);
+    }
+    let mut sum = 0;
+    let mut common = ItemMask::ALL;
+    let mut group = (0, 0);
+    for (line, items) in rucksacks(input) {
+        let (first_line, rucksacks) = group;
+        group = (if rucksacks == 0 { line } else { first_line }, rucksacks + 1);
+        common = common.intersection(str_to_mask(line, items)?);
+        if group.1 == group_size {
+            sum += only_item(group.0, common)?;
+            common = ItemMask::ALL;
+            group = (0, 0);
+        }
+    }
+    match group {
+        (_, 0) => Ok(sum),
+        (line, rucksacks) => Err(RucksackError::IncompleteGroup { line, rucksacks, group_size }),
+    }
 }
 
-fn lines_to_common_value(lines: &[&str]) -> u32 {
-    let x = str_to_value_set(lines[0]);
-    let y = str_to_value_set(lines[1]);
-    let z = str_to_value_set(lines[2]);
-    intersect(x, intersect(y, z)).iter().cloned().next().unwrap()
+fn common_in_compartments(line: usize, items: &str, compartments: usize) -> Result<u32, RucksackError> {
+    // Checked first, so that the items are all one byte and it's safe to split by bytes
+    let all = str_to_mask(line, items)?;
+    if compartments == 0 || !items.len().is_multiple_of(compartments) {
+        return Err(RucksackError::UnevenCompartments { line, items: items.len(), compartments });
+    }
+    let size = items.len() / compartments;
+    let common = (0..compartments).fold(all, |common, c| {
+        common.intersection(ItemMask::from_priorities(items[c * size..(c + 1) * size].chars().filter_map(to_value)))
+    });
+    only_item(line, common)
 }
 
-fn str_to_value_set(s: &str) -> HashSet<u32> {
-    HashSet::from_iter(s.chars().map(to_value))
+pub fn only_item(line: usize, common: ItemMask) -> Result<u32, RucksackError> {
+    match common.len() {
+        0 => Err(RucksackError::NoCommonItem { line }),
+        1 => Ok(common.iter().next().unwrap()),
+        _ => Err(RucksackError::SeveralCommonItems { line, items: common.iter().map(to_item).collect() }),
+    }
 }
 
-fn intersect(l: HashSet<u32>, r: HashSet<u32>) -> HashSet<u32> {
-    l.intersection(&r).cloned().collect()
+pub fn str_to_mask(line: usize, s: &str) -> Result<ItemMask, RucksackError> {
+    s.chars().try_fold(ItemMask::default(), |mask, c| {
+        to_value(c).map(|p| mask.with(p)).ok_or(RucksackError::InvalidItem { line, item: c })
+    })
 }
 
 // https://www.utf8-chartable.de/
-fn to_value(c: char) -> u32 {
-    if c.is_lowercase() {
+pub fn to_value(c: char) -> Option<u32> {
+    match c {
         // Subtract the UTF8 hex value to convert a-z to 1-26
-        c as u32 - 0x61 + 1
-    } else {
+        'a'..='z' => Some(c as u32 - 0x61 + 1),
         // Subtract the UTF8 hex value, and add 26 to convert A-Z to 27-52
-        c as u32 - 0x41 + 1 + 26
+        'A'..='Z' => Some(c as u32 - 0x41 + 1 + 26),
+        _ => None,
+    }
+}
+
+// The item type with the priority
+pub fn to_item(priority: u32) -> char {
+    match priority {
+        1..=26 => char::from_u32(priority - 1 + 0x61).unwrap(),
+        _ => char::from_u32(priority - 27 + 0x41).unwrap(),
     }
 }
 
+#[cfg(test)]
+mod test {
+    use super::RucksackError::*;
+    use crate::memory::measure;
+
+    const TEST_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(super::part1(TEST_INPUT), 157);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(super::part2(TEST_INPUT), 70);
+    }
+
+    #[test]
+    fn test_configurable_sizes() {
+        // abcd in four compartments has nothing in common, aaaa has a
+        assert_eq!(super::sum_compartments("aaaa\nbbbbbbbb", 4), Ok(3));
+        assert_eq!(super::sum_compartments("abcab", 1), Err(SeveralCommonItems { line: 1, items: vec!['a', 'b', 'c'] }));
+        assert_eq!(super::sum_groups("ab\nbc\nbd\nbe", 4), Ok(2));
+        assert_eq!(super::sum_groups("ab\nbc\nde\nfg", 2), Err(NoCommonItem { line: 3 }));
+        assert_eq!(super::sum_groups(TEST_INPUT, 1), Err(SeveralCommonItems { line: 1, items: "cfghprstvwFJMW".chars().collect() }));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(super::sum_compartments("abc", 2), Err(UnevenCompartments { line: 1, items: 3, compartments: 2 }));
+        assert_eq!(super::sum_compartments("aa\nab", 2), Err(NoCommonItem { line: 2 }));
+        assert_eq!(super::sum_compartments("aa\n\na1a1", 2), Err(InvalidItem { line: 3, item: '1' }));
+        assert_eq!(super::sum_groups("ab\nbc\nbd\nbe", 3), Err(IncompleteGroup { line: 4, rucksacks: 1, group_size: 3 }));
+        assert_eq!(super::sum_groups("ab\nbc", 0), Err(EmptyGroups));
+        assert_eq!(
+            InvalidItem { line: 3, item: 'é' }.to_string(),
+            "line 3: 'é' isn't an item type, they're a-z and A-Z"
+        );
+    }
+
+    #[test]
+    fn test_no_allocations() {
+        let (answers, usage) = measure(|| (super::part1(TEST_INPUT), super::part2(TEST_INPUT)));
+        assert_eq!(answers, (157, 70));
+        assert_eq!(usage.allocations, 0);
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(super::part1(super::INPUT), 7831);
+        assert_eq!(super::part2(super::INPUT), 2683);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crate::interval::{Interval, IntervalSet};
+
+// How the whole camp's assignments cover the sections
+#[derive(Debug, PartialEq)]
+pub struct Coverage {
+    // From the lowest section anyone cleans to the highest
+    pub span: Option<Interval>,
+    pub k: usize,
+    // The sections at least k elves clean
+    pub at_least_k: IntervalSet,
+    // The sections in the span nobody cleans
+    pub uncovered: IntervalSet,
+    pub max_elves: usize,
+    // Where max_elves of them are cleaning the same sections
+    pub busiest: IntervalSet,
+}
+
+// Sweeps across the sections once, after sorting where each assignment starts and stops,
+// so it's O(n log n) in the number of assignments however far apart the sections are
+pub fn coverage(assignments: impl IntoIterator<Item = Interval>, k: usize) -> Coverage {
+    // Every assignment adds an elf at its start and takes one away after its end. Past the
+    // end can be one more than i64 holds, so they're i128.
+    let mut events: Vec<(i128, isize)> =
+        assignments.into_iter().flat_map(|a| [(a.start as i128, 1), (a.end as i128 + 1, -1)]).collect();
+    events.sort_unstable();
+
+    let mut result = Coverage {
+        span: None,
+        k,
+        at_least_k: IntervalSet::new(),
+        uncovered: IntervalSet::new(),
+        max_elves: 0,
+        busiest: IntervalSet::new(),
+    };
+    let mut elves: isize = 0;
+    let mut idx = 0;
+    while idx < events.len() {
+        let at = events[idx].0;
+        while idx < events.len() && events[idx].0 == at {
+            elves += events[idx].1;
+            idx += 1;
+        }
+        // The count holds until the next event, the last one always brings it back to zero
+        let Some(&(next, _)) = events.get(idx) else {
+            break;
+        };
+        // Both fit back in an i64, only the last event can be past i64::MAX
+        let sections = Interval { start: at as i64, end: (next - 1) as i64 };
+        let elves = elves as usize;
+        if elves == 0 {
+            result.uncovered.insert(sections);
+        }
+        if elves >= k {
+            result.at_least_k.insert(sections);
+        }
+        if elves > result.max_elves {
+            result.max_elves = elves;
+            result.busiest = IntervalSet::new();
+        }
+        if elves == result.max_elves && elves > 0 {
+            result.busiest.insert(sections);
+        }
+    }
+    result.span = events.first().zip(events.last()).map(|((start, _), (end, _))| Interval { start: *start as i64, end: (end - 1) as i64 });
+    result
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(span) = self.span else {
+            return writeln!(f, "Nobody has any sections to clean");
+        };
+        writeln!(f, "Sections {} are assigned", span)?;
+        writeln!(f, "{} sections have at least {} elves: {}", self.at_least_k.len(), self.k, self.at_least_k)?;
+        writeln!(f, "{} sections have nobody: {}", self.uncovered.len(), self.uncovered)?;
+        writeln!(f, "At most {} elves clean the same section, in {}", self.max_elves, self.busiest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        day04::solution::{get_assignment_pairs, get_lines},
+        interval::i,
+    };
+
+    const TEST_INPUT: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    #[test]
+    fn test_coverage() {
+        let pairs = get_assignment_pairs(&get_lines(TEST_INPUT)).unwrap();
+        let coverage = coverage(pairs.iter().flat_map(|(one, two)| [one.sections, two.sections]), 5);
+        // Sections 2 to 9 have 4, 5, 7, 7, 8, 6, 4 and 1 elves
+        assert_eq!(coverage.span, Some(i(2, 9)));
+        assert_eq!(coverage.at_least_k.ranges(), &[i(3, 7)]);
+        assert!(coverage.uncovered.is_empty());
+        assert_eq!(coverage.max_elves, 8);
+        assert_eq!(coverage.busiest.ranges(), &[i(6, 6)]);
+    }
+
+    #[test]
+    fn test_gaps_and_ties() {
+        let coverage = coverage([i(1, 2), i(8, 9), i(5, 6), i(2, 2), i(9, 9)], 1);
+        assert_eq!(coverage.span, Some(i(1, 9)));
+        assert_eq!(coverage.uncovered.ranges(), &[i(3, 4), i(7, 7)]);
+        assert_eq!(coverage.at_least_k.len(), 6);
+        assert_eq!((coverage.max_elves, coverage.busiest.ranges()), (2, &[i(2, 2), i(9, 9)][..]));
+    }
+
+    #[test]
+    fn test_full_range() {
+        let coverage = coverage([i(i64::MAX - 1, i64::MAX), i(i64::MIN, i64::MAX), i(i64::MAX, i64::MAX)], 3);
+        assert_eq!(coverage.span, Some(i(i64::MIN, i64::MAX)));
+        assert_eq!(coverage.at_least_k.ranges(), &[i(i64::MAX, i64::MAX)]);
+        assert_eq!(coverage.busiest.ranges(), &[i(i64::MAX, i64::MAX)]);
+        assert!(coverage.uncovered.is_empty());
+        assert_eq!(coverage.to_string().lines().nth(1), Some("1 sections have at least 3 elves: 9223372036854775807-9223372036854775807"));
+    }
+
+    #[test]
+    fn test_nobody() {
+        let coverage = coverage([], 1);
+        assert_eq!((coverage.span, coverage.max_elves), (None, 0));
+        assert_eq!(coverage.to_string(), "Nobody has any sections to clean\n");
+    }
+}
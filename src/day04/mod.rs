@@ -1 +1,3 @@
-pub mod solution;
\ No newline at end of file
+pub mod coverage;
+pub mod overlap;
+pub mod solution;
@@ -0,0 +1,111 @@
+use super::solution::Assignment;
+use crate::interval::Interval;
+
+// The elf that isn't needed, because the other cleans all of their sections too
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Redundant {
+    First,
+    Second,
+    // They have the same sections, so either can go
+    Either,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PairOverlap {
+    pub first: Interval,
+    pub second: Interval,
+    pub overlap: Option<Interval>,
+    pub redundant: Option<Redundant>,
+}
+
+impl PairOverlap {
+    pub fn overlap_len(&self) -> u128 {
+        self.overlap.map_or(0, |o| o.len())
+    }
+
+    // The sections cleaning drops by if the redundant elf stays away
+    pub fn freed(&self) -> u128 {
+        match self.redundant {
+            Some(Redundant::First | Redundant::Either) => self.first.len(),
+            Some(Redundant::Second) => self.second.len(),
+            None => 0,
+        }
+    }
+}
+
+pub fn overlaps(pairs: &[(Assignment, Assignment)]) -> Vec<PairOverlap> {
+    pairs
+        .iter()
+        .map(|(one, two)| {
+            let (first, second) = (one.sections, two.sections);
+            let redundant = match (second.contains(&first), first.contains(&second)) {
+                (true, true) => Some(Redundant::Either),
+                (true, false) => Some(Redundant::First),
+                (false, true) => Some(Redundant::Second),
+                (false, false) => None,
+            };
+            PairOverlap { first, second, overlap: first.intersection(&second), redundant }
+        })
+        .collect()
+}
+
+pub fn to_text(overlaps: &[PairOverlap]) -> String {
+    let mut text = format!("{:>5}  {:>7}  {:>7}  {:>7}  {:>6}  {:>9}  {:>5}\n", "Pair", "First", "Second", "Overlap", "Length", "Redundant", "Freed");
+    for (idx, o) in overlaps.iter().enumerate() {
+        let overlap = o.overlap.map_or("-".to_string(), |o| o.to_string());
+        let redundant = match o.redundant {
+            Some(Redundant::First) => "first",
+            Some(Redundant::Second) => "second",
+            Some(Redundant::Either) => "either",
+            None => "-",
+        };
+        text.push_str(&format!(
+            "{:>5}  {:>7}  {:>7}  {:>7}  {:>6}  {:>9}  {:>5}\n",
+            idx + 1,
+            o.first.to_string(),
+            o.second.to_string(),
+            overlap,
+            o.overlap_len(),
+            redundant,
+            o.freed()
+        ));
+    }
+    let freed: u128 = overlaps.iter().map(PairOverlap::freed).sum();
+    text.push_str(&format!("Dropping the redundant elves frees {} sections\n", freed));
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        day04::solution::{get_assignment_pairs, get_lines},
+        interval::i,
+    };
+
+    const TEST_INPUT: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    #[test]
+    fn test_overlaps() {
+        let overlaps = overlaps(&get_assignment_pairs(&get_lines(TEST_INPUT)).unwrap());
+        assert_eq!(overlaps[0], PairOverlap { first: i(2, 4), second: i(6, 8), overlap: None, redundant: None });
+        assert_eq!((overlaps[2].overlap, overlaps[2].overlap_len(), overlaps[2].freed()), (Some(i(7, 7)), 1, 0));
+        // 3-7 is inside 2-8, and 6-6 inside 4-6
+        assert_eq!((overlaps[3].redundant, overlaps[3].freed()), (Some(Redundant::Second), 5));
+        assert_eq!((overlaps[4].redundant, overlaps[4].freed()), (Some(Redundant::First), 1));
+        assert_eq!(overlaps.iter().filter(|o| o.redundant.is_some()).count(), 2);
+        assert_eq!(overlaps.iter().filter(|o| o.overlap.is_some()).count(), 4);
+    }
+
+    #[test]
+    fn test_identical_pair() {
+        let overlaps = overlaps(&get_assignment_pairs(&get_lines("3-5,3-5")).unwrap());
+        assert_eq!((overlaps[0].redundant, overlaps[0].freed()), (Some(Redundant::Either), 3));
+        assert!(to_text(&overlaps).ends_with("Dropping the redundant elves frees 3 sections\n"));
+    }
+}
@@ -1,69 +1,89 @@
-const INPUT: &str = include_str!("input.txt");
+use std::fmt;
 
+use crate::interval::Interval;
 
+pub const INPUT: &str = include_str!("input.txt");
 
-struct Assignment {
-    pub start: u32,
-    pub end: u32,
+// The sections an elf has to clean
+pub struct Assignment {
+    pub sections: Interval,
 }
 
 pub fn solution() {
-    let lines: Vec<&str> = INPUT.split('\n').filter(|s| !s.is_empty()).collect();
+    let lines = get_lines(INPUT);
     println!("Solution for day four part one {}", part1(&lines));
     println!("Solution for day four part two {}", part2(&lines));
 }
 
-impl Assignment {
-    pub fn overlaps_with_fully(&self, other: &Self) -> bool {
-        (self.start >= other.start && self.end <= other.end)
-            || (self.start <= other.start && self.end >= other.end)
+// The lines with their line numbers, skipping blank ones
+pub fn get_lines(input: &str) -> Vec<(usize, &str)> {
+    input.split('\n').enumerate().map(|(idx, s)| (idx + 1, s.trim_end())).filter(|(_, s)| !s.is_empty()).collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AssignmentError {
+    // Not two assignments separated by a comma
+    NotAPair { line: usize },
+    // Not two section numbers separated by a dash
+    Malformed { line: usize, text: String },
+    Backwards { line: usize, start: u32, end: u32 },
+}
+
+impl fmt::Display for AssignmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAPair { line } => write!(f, "line {}: expected two assignments separated by a comma", line),
+            Self::Malformed { line, text } => write!(f, "line {}: {:?} isn't a range of sections like 2-4", line, text),
+            Self::Backwards { line, start, end } => write!(f, "line {}: the assignment {}-{} starts after it ends", line, start, end),
+        }
     }
+}
 
-    fn check_overlap(&self, other: &Self) -> bool {
-        (self.start >= other.start && self.start <= other.end)
-        || (self.end >= other.start && self.end <= other.end)
+impl Assignment {
+    pub fn overlaps_with_fully(&self, other: &Self) -> bool {
+        self.sections.contains(&other.sections) || other.sections.contains(&self.sections)
     }
 
     pub fn overlaps_with_at_all(&self, other: &Self) -> bool {
-        self.check_overlap(other) || other.check_overlap(self)
+        self.sections.overlaps(&other.sections)
     }
 }
 
-impl TryFrom<&str> for Assignment {
-    type Error = &'static str;
-    fn try_from(s: &str) -> Result<Self, &'static str> {
-        let parts: Vec<&str> = s.split('-').collect();
-        if parts.len() != 2 {
-            return Err("Error parsing.");
-        }
-        let start = parts[0].parse::<u32>().map_err(|_| "Error parsing u32")?;
-        let end = parts[1].parse::<u32>().map_err(|_| "Error parsing u32")?;
-        Ok(Assignment { start, end })
+// The line number goes with the text, for the errors
+impl TryFrom<(usize, &str)> for Assignment {
+    type Error = AssignmentError;
+    fn try_from((line, s): (usize, &str)) -> Result<Self, AssignmentError> {
+        let malformed = || AssignmentError::Malformed { line, text: s.to_string() };
+        let (start, end) = s.split_once('-').ok_or_else(malformed)?;
+        let start = start.parse::<u32>().map_err(|_| malformed())?;
+        let end = end.parse::<u32>().map_err(|_| malformed())?;
+        let sections = Interval::new(start as i64, end as i64).ok_or(AssignmentError::Backwards { line, start, end })?;
+        Ok(Assignment { sections })
     }
 }
 
-fn get_assignment_pairs(lines: &[&str]) -> Vec<(Assignment, Assignment)> {
-    lines.iter().map(|s| {
-        let parts: Vec<&str> = s.split(',').collect();
-        if parts.len() != 2 {
-            panic!("Error parsing.");
-        }
-        (parts[0], parts[1])
-    })
-    .map(|(one,two)| (
-        Assignment::try_from(one).unwrap(),
-        Assignment::try_from(two).unwrap()
-    )).collect()
+pub fn get_assignment_pairs(lines: &[(usize, &str)]) -> Result<Vec<(Assignment, Assignment)>, AssignmentError> {
+    lines
+        .iter()
+        .map(|(line, s)| {
+            let (one, two) = s.split_once(',').ok_or(AssignmentError::NotAPair { line: *line })?;
+            Ok((Assignment::try_from((*line, one))?, Assignment::try_from((*line, two))?))
+        })
+        .collect()
 }
 
-fn part1(lines: &[&str]) -> u32 {
-    get_assignment_pairs(lines).iter()
+fn pairs(lines: &[(usize, &str)]) -> Vec<(Assignment, Assignment)> {
+    get_assignment_pairs(lines).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn part1(lines: &[(usize, &str)]) -> u32 {
+    pairs(lines).iter()
     .filter(|(one,two)| one.overlaps_with_fully(two))
     .count() as u32
 }
 
-fn part2(lines: &[&str]) -> u32 {
-    get_assignment_pairs(lines).iter()
+pub fn part2(lines: &[(usize, &str)]) -> u32 {
+    pairs(lines).iter()
     .filter(|(one,two)| one.overlaps_with_at_all(two))
     .count() as u32
 }
@@ -81,13 +101,39 @@ mod test {
 
     #[test]
     fn test_part1() {
-        let lines: Vec<&str> = TEST_INPUT.split('\n').filter(|s| !s.is_empty()).collect();
+        let lines = get_lines(TEST_INPUT);
         assert_eq!(part1(&lines), 2);
     }
 
+    #[test]
+    fn test_assignment() {
+        let one = Assignment::try_from((1, "2-8")).unwrap();
+        let two = Assignment::try_from((1, "3-7")).unwrap();
+        assert!(one.overlaps_with_fully(&two) && two.overlaps_with_fully(&one));
+    }
+
+    #[test]
+    fn test_errors() {
+        let errors = |input| get_assignment_pairs(&get_lines(input)).err();
+        assert_eq!(errors("2-4,6-8\n\n3-7,7-3"), Some(AssignmentError::Backwards { line: 3, start: 7, end: 3 }));
+        assert_eq!(errors("2-4,6-8\n2-4"), Some(AssignmentError::NotAPair { line: 2 }));
+        assert_eq!(errors("2-4,6_8"), Some(AssignmentError::Malformed { line: 1, text: "6_8".to_string() }));
+        assert_eq!(
+            AssignmentError::Backwards { line: 3, start: 7, end: 3 }.to_string(),
+            "line 3: the assignment 7-3 starts after it ends"
+        );
+    }
+
     #[test]
     fn test_part2() {
-        let lines: Vec<&str> = TEST_INPUT.split('\n').filter(|s| !s.is_empty()).collect();
+        let lines = get_lines(TEST_INPUT);
         assert_eq!(part2(&lines), 4);
     }
+
+    #[test]
+    fn test_answers() {
+        let lines = get_lines(INPUT);
+        assert_eq!(part1(&lines), 582);
+        assert_eq!(part2(&lines), 893);
+    }
 }
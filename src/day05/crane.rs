@@ -0,0 +1,166 @@
+// How a crane moves crates. Every instruction is carried out as a series of lifts, each
+// taking some crates off the top of one stack and putting them down on another in the
+// same order, so the cranes only differ in how many crates they take in a lift.
+pub trait Crane {
+    fn name(&self) -> String;
+    // How many crates the next lift takes, with this many left to move, at least one
+    fn lift_size(&self, remaining: u32) -> u32;
+    // Called after every lift, for the cranes that keep count
+    fn lifted(&mut self, _crates: u32) {}
+}
+
+// One crate at a time, so the crates end up in reverse order
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn lift_size(&self, _: u32) -> u32 {
+        1
+    }
+}
+
+// All the crates at once, so they keep their order
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn lift_size(&self, remaining: u32) -> u32 {
+        remaining
+    }
+}
+
+// Up to k crates at a time, a CrateMover 9000 when k is 1
+pub struct Limited {
+    pub per_lift: u32,
+}
+
+impl Crane for Limited {
+    fn name(&self) -> String {
+        format!("{}-per-lift crane", self.per_lift)
+    }
+
+    fn lift_size(&self, remaining: u32) -> u32 {
+        remaining.min(self.per_lift).max(1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LiftCost {
+    pub per_lift: u64,
+    pub per_crate: u64,
+}
+
+// Any crane, keeping count of the lifts it makes and the energy they take
+pub struct Metered {
+    pub crane: Box<dyn Crane>,
+    pub cost: LiftCost,
+    pub lifts: u64,
+    pub crates: u64,
+    pub energy: u64,
+}
+
+impl Metered {
+    pub fn new(crane: Box<dyn Crane>, cost: LiftCost) -> Self {
+        Self { crane, cost, lifts: 0, crates: 0, energy: 0 }
+    }
+}
+
+impl Crane for Metered {
+    fn name(&self) -> String {
+        self.crane.name()
+    }
+
+    fn lift_size(&self, remaining: u32) -> u32 {
+        self.crane.lift_size(remaining)
+    }
+
+    fn lifted(&mut self, crates: u32) {
+        self.crane.lifted(crates);
+        self.lifts += 1;
+        self.crates += crates as u64;
+        self.energy += self.cost.per_lift + self.cost.per_crate * crates as u64;
+    }
+}
+
+// The sizes of the lifts the crane makes to move the crates
+pub fn lifts(crane: &dyn Crane, num: u32) -> Vec<u32> {
+    let mut sizes = Vec::new();
+    let mut remaining = num;
+    while remaining > 0 {
+        let size = crane.lift_size(remaining).clamp(1, remaining);
+        sizes.push(size);
+        remaining -= size;
+    }
+    sizes
+}
+
+// 9000, 9001, or k-per-lift for a crane that takes up to k crates at a time
+pub fn by_name(name: &str) -> Option<Box<dyn Crane>> {
+    match name {
+        "9000" => Some(Box::new(CrateMover9000)),
+        "9001" => Some(Box::new(CrateMover9001)),
+        _ => {
+            let per_lift = name.strip_suffix("-per-lift")?.parse::<u32>().ok().filter(|k| *k > 0)?;
+            Some(Box::new(Limited { per_lift }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day05::{
+        fixtures::{stacks, TEST_INPUT},
+        solution::{get_inputs, Instruction},
+    };
+
+    fn run(crane: &mut dyn Crane) -> String {
+        let (mut crate_stack, instructions) = get_inputs(TEST_INPUT);
+        instructions.iter().for_each(|i| crate_stack.apply(crane, i).unwrap());
+        crate_stack.get_top_crates()
+    }
+
+    #[test]
+    fn test_cranes() {
+        assert_eq!(run(&mut CrateMover9000), "CMZ");
+        assert_eq!(run(&mut CrateMover9001), "MCD");
+        assert_eq!(run(&mut Limited { per_lift: 1 }), "CMZ");
+        assert_eq!(run(&mut Limited { per_lift: 3 }), "MCD");
+        // The three crates go over as two then one, so D lands on top of N and Z
+        assert_eq!(run(&mut Limited { per_lift: 2 }), "MCZ");
+    }
+
+    #[test]
+    fn test_limited_lift_order() {
+        let mut crate_stack = stacks(&["ABCDE", ""]);
+        crate_stack.apply(&mut Limited { per_lift: 2 }, &Instruction { num: 5, from: 1, to: 2 }).unwrap();
+        let moved: String = crate_stack.stacks[1].iter().map(|c| c.0).collect();
+        assert_eq!(moved, "DEBCA");
+    }
+
+    #[test]
+    fn test_metered() {
+        let cost = LiftCost { per_lift: 10, per_crate: 1 };
+        let mut crane = Metered::new(Box::new(CrateMover9000), cost);
+        run(&mut crane);
+        assert_eq!((crane.lifts, crane.crates, crane.energy), (7, 7, 77));
+
+        let mut crane = Metered::new(Box::new(CrateMover9001), cost);
+        run(&mut crane);
+        assert_eq!((crane.lifts, crane.crates, crane.energy), (4, 7, 47));
+    }
+
+    #[test]
+    fn test_by_name() {
+        assert_eq!(by_name("9001").unwrap().name(), "CrateMover 9001");
+        assert_eq!(by_name("4-per-lift").unwrap().lift_size(9), 4);
+        assert!(by_name("0-per-lift").is_none());
+        assert!(by_name("9002").is_none());
+    }
+}
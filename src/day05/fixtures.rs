@@ -0,0 +1,28 @@
+// What the day05 tests share, the puzzle's example and ways to set up and move stacks
+
+use super::{
+    crane::Crane,
+    solution::{Crate, CrateStack, Instruction},
+};
+
+pub const TEST_INPUT: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+// A stack for each string, its crates from the bottom up
+pub fn stacks(stacks: &[&str]) -> CrateStack {
+    CrateStack { stacks: stacks.iter().map(|s| s.chars().map(Crate).collect()).collect() }
+}
+
+// Where the instructions leave the stacks
+pub fn follow(start: &CrateStack, instructions: &[Instruction], crane: &mut dyn Crane) -> CrateStack {
+    let mut crate_stack = start.clone();
+    instructions.iter().for_each(|i| crate_stack.apply(crane, i).unwrap());
+    crate_stack
+}
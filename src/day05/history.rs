@@ -0,0 +1,257 @@
+use std::fmt;
+
+use super::{
+    crane::Crane,
+    solution::{Crate, CrateStack, Instruction, InstructionError, MoveError},
+};
+
+// What happened to the top of a stack, the crates below them weren't touched
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackChange {
+    // Numbered from 1, like the instructions
+    pub stack: usize,
+    // Bottom to top, as they were before and after
+    pub removed: Vec<Crate>,
+    pub added: Vec<Crate>,
+}
+
+// The stacks an instruction changed, and enough to put them back
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diff {
+    pub instruction: Instruction,
+    pub changes: Vec<StackChange>,
+}
+
+impl Diff {
+    fn redo(&self, crate_stack: &mut CrateStack) {
+        for c in &self.changes {
+            let stack = &mut crate_stack.stacks[c.stack - 1];
+            stack.truncate(stack.len() - c.removed.len());
+            stack.extend(&c.added);
+        }
+    }
+
+    fn undo(&self, crate_stack: &mut CrateStack) {
+        for c in self.changes.iter().rev() {
+            let stack = &mut crate_stack.stacks[c.stack - 1];
+            stack.truncate(stack.len() - c.added.len());
+            stack.extend(&c.removed);
+        }
+    }
+}
+
+// The stacks as a list of instructions is followed, able to go back and forth through it
+pub struct History {
+    stacks: CrateStack,
+    crane: Box<dyn Crane>,
+    instructions: Vec<Instruction>,
+    // The changes made by the instructions carried out so far, kept when they're undone
+    diffs: Vec<Diff>,
+    // How many instructions have been applied
+    position: usize,
+}
+
+impl History {
+    pub fn new(stacks: CrateStack, instructions: Vec<Instruction>, crane: Box<dyn Crane>) -> Self {
+        Self { stacks, crane, instructions, diffs: Vec::new(), position: 0 }
+    }
+
+    pub fn stacks(&self) -> &CrateStack {
+        &self.stacks
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    // The changes the nth instruction made, counting from 1, once it's been carried out
+    pub fn diff(&self, step: usize) -> Option<&Diff> {
+        step.checked_sub(1).and_then(|idx| self.diffs.get(idx))
+    }
+
+    // Carry out a new instruction here, dropping the ones that would have come after it.
+    // For trying out moves by hand, the puzzle only ever follows its own instructions.
+    // If the move can't be made nothing changes, and what was undone can still be redone.
+    pub fn apply(&mut self, instruction: Instruction) -> Result<&Diff, MoveError> {
+        let diff = changes(&mut self.stacks, self.crane.as_mut(), &instruction)?;
+        self.instructions.truncate(self.position);
+        self.diffs.truncate(self.position);
+        self.instructions.push(instruction);
+        self.diffs.push(diff);
+        self.position += 1;
+        Ok(&self.diffs[self.position - 1])
+    }
+
+    pub fn undo(&mut self) -> Option<&Diff> {
+        self.position = self.position.checked_sub(1)?;
+        let diff = &self.diffs[self.position];
+        diff.undo(&mut self.stacks);
+        Some(diff)
+    }
+
+    // Carry out the next instruction, None at the end of them
+    pub fn redo(&mut self) -> Result<Option<&Diff>, MoveError> {
+        let Some(instruction) = self.instructions.get(self.position) else {
+            return Ok(None);
+        };
+        if self.position == self.diffs.len() {
+            let diff = changes(&mut self.stacks, self.crane.as_mut(), instruction)?;
+            self.diffs.push(diff);
+        } else {
+            self.diffs[self.position].redo(&mut self.stacks);
+        }
+        self.position += 1;
+        Ok(Some(&self.diffs[self.position - 1]))
+    }
+
+    // Go to just after the nth instruction, or the start for 0. The line in the error counts
+    // from the first instruction.
+    pub fn seek(&mut self, step: usize) -> Result<(), InstructionError> {
+        let step = step.min(self.instructions.len());
+        while self.position > step {
+            self.undo();
+        }
+        while self.position < step {
+            let line = self.position + 1;
+            self.redo().map_err(|error| InstructionError { line, error })?;
+        }
+        Ok(())
+    }
+}
+
+// Apply the instruction, and work out what it changed from the stacks it touched
+fn changes(crate_stack: &mut CrateStack, crane: &mut dyn Crane, instruction: &Instruction) -> Result<Diff, MoveError> {
+    let mut touched = vec![instruction.from as usize, instruction.to as usize];
+    touched.dedup();
+    let before: Vec<Vec<Crate>> = touched.iter().map(|s| crate_stack.stacks.get(s - 1).cloned().unwrap_or_default()).collect();
+    crate_stack.apply(crane, instruction)?;
+    let changes = touched
+        .into_iter()
+        .zip(before)
+        .filter_map(|(stack, before)| {
+            let after = &crate_stack.stacks[stack - 1];
+            let same = before.iter().zip(after).take_while(|(b, a)| b == a).count();
+            (same < before.len() || same < after.len())
+                .then(|| StackChange { stack, removed: before[same..].to_vec(), added: after[same..].to_vec() })
+        })
+        .collect();
+    Ok(Diff { instruction: instruction.clone(), changes })
+}
+
+impl fmt::Display for StackChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let crates = |crates: &[Crate]| crates.iter().map(|c| c.0).collect::<String>();
+        match (self.removed.is_empty(), self.added.is_empty()) {
+            (false, true) => write!(f, "stack {} -{}", self.stack, crates(&self.removed)),
+            (true, false) => write!(f, "stack {} +{}", self.stack, crates(&self.added)),
+            _ => write!(f, "stack {} -{} +{}", self.stack, crates(&self.removed), crates(&self.added)),
+        }
+    }
+}
+
+impl Diff {
+    pub fn describe_changes(&self) -> String {
+        match self.changes.is_empty() {
+            true => "nothing changed".to_string(),
+            false => self.changes.iter().map(StackChange::to_string).collect::<Vec<String>>().join(", "),
+        }
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.instruction, self.describe_changes())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day05::{
+        crane::{CrateMover9000, CrateMover9001},
+        fixtures::TEST_INPUT,
+        solution::get_inputs,
+    };
+
+    fn history(crane: Box<dyn Crane>) -> History {
+        let (crate_stack, instructions) = get_inputs(TEST_INPUT);
+        History::new(crate_stack, instructions, crane)
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = history(Box::new(CrateMover9000));
+        let start = history.stacks().clone();
+        assert_eq!(history.undo(), None);
+
+        assert_eq!(history.redo().unwrap().unwrap().to_string(), "move 1 from 2 to 1: stack 2 -D, stack 1 +D");
+        assert_eq!(history.redo().unwrap().unwrap().to_string(), "move 3 from 1 to 3: stack 1 -ZND, stack 3 +DNZ");
+        let after_two = history.stacks().clone();
+        history.undo();
+        history.undo();
+        assert_eq!(history.stacks(), &start);
+        history.redo().unwrap();
+        history.redo().unwrap();
+        assert_eq!(history.stacks(), &after_two);
+    }
+
+    #[test]
+    fn test_seek() {
+        for (crane, top) in [(Box::new(CrateMover9000) as Box<dyn Crane>, "CMZ"), (Box::new(CrateMover9001), "MCD")] {
+            let mut history = history(crane);
+            let start = history.stacks().clone();
+            history.seek(10).unwrap();
+            assert_eq!((history.position(), history.stacks().get_top_crates().as_str()), (4, top));
+            history.seek(1).unwrap();
+            assert_eq!(history.stacks().get_top_crates(), "DCP");
+            history.seek(0).unwrap();
+            assert_eq!(history.stacks(), &start);
+        }
+    }
+
+    #[test]
+    fn test_apply_drops_the_rest() {
+        let mut history = history(Box::new(CrateMover9001));
+        history.seek(4).unwrap();
+        history.seek(2).unwrap();
+        let diff = history.apply(Instruction { num: 1, from: 3, to: 3 }).unwrap();
+        // Lifting a crate and putting it back down changes nothing
+        assert_eq!(diff.changes, vec![]);
+        assert_eq!(history.instructions().len(), 3);
+        assert_eq!(history.redo(), Ok(None));
+        assert_eq!(history.diff(3).unwrap().to_string(), "move 1 from 3 to 3: nothing changed");
+
+        assert_eq!(history.apply(Instruction { num: 1, from: 4, to: 1 }), Err(MoveError::NoSuchStack { stack: 4, stacks: 3 }));
+        assert_eq!(history.instructions().len(), 3);
+    }
+
+    #[test]
+    fn test_failed_apply_keeps_redo() {
+        let mut history = history(Box::new(CrateMover9000));
+        history.seek(4).unwrap();
+        let end = history.stacks().clone();
+        history.undo();
+        let before = history.stacks().clone();
+        let error = history.apply(Instruction { num: 9, from: 1, to: 2 });
+        assert_eq!(error, Err(MoveError::NotEnoughCrates { stack: 1, crates: 2, wanted: 9 }));
+        assert_eq!(history.stacks(), &before);
+        assert_eq!(history.redo().unwrap().unwrap().instruction, Instruction { num: 1, from: 1, to: 2 });
+        assert_eq!(history.stacks(), &end);
+    }
+
+    #[test]
+    fn test_seek_error() {
+        let (crate_stack, _) = get_inputs(TEST_INPUT);
+        let instructions = vec![Instruction { num: 1, from: 1, to: 2 }, Instruction { num: 2, from: 1, to: 2 }];
+        let mut history = History::new(crate_stack, instructions, Box::new(CrateMover9000));
+        assert_eq!(
+            history.seek(2),
+            Err(InstructionError { line: 2, error: MoveError::NotEnoughCrates { stack: 1, crates: 1, wanted: 2 } })
+        );
+        assert_eq!(history.position(), 1);
+    }
+}
@@ -1 +1,9 @@
-pub mod solution;
\ No newline at end of file
+pub mod crane;
+#[cfg(test)]
+mod fixtures;
+pub mod history;
+pub mod planner;
+pub mod render;
+pub mod reverse;
+pub mod solution;
+pub mod view;
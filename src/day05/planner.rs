@@ -0,0 +1,188 @@
+use priority_queue::PriorityQueue;
+use std::{collections::HashMap, fmt};
+
+use super::{
+    crane::{lifts, Crane},
+    solution::{Crate, CrateStack, Instruction},
+};
+
+// How many arrangements to look at before giving up
+pub const DEFAULT_MAX_STATES: usize = 200_000;
+
+#[derive(Debug, PartialEq)]
+pub enum PlanError {
+    DifferentStacks { start: usize, goal: usize },
+    // The goal has crates the start doesn't, or the other way round
+    DifferentCrates,
+    // Every arrangement the crane can reach has been tried
+    Unreachable,
+    TooManyStates { explored: usize },
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DifferentStacks { start, goal } => write!(f, "the start has {} stacks and the goal has {}", start, goal),
+            Self::DifferentCrates => write!(f, "the start and the goal don't have the same crates"),
+            Self::Unreachable => write!(f, "the crane can't get from the start to the goal"),
+            Self::TooManyStates { explored } => write!(f, "gave up after looking at {} arrangements", explored),
+        }
+    }
+}
+
+// A shortest list of instructions that turns the start into the goal with the crane, by A*
+// over the arrangements of crates. Every instruction costs the same, however many crates
+// it moves, so counting misplaced crates could overestimate. Instead it counts the stacks
+// that have crates to lose and the stacks that have crates to gain. An instruction takes
+// from one stack and gives to one, so needs at least the larger of the two.
+pub fn plan(start: &CrateStack, goal: &CrateStack, crane: &dyn Crane, max_states: usize) -> Result<Vec<Instruction>, PlanError> {
+    if start.stacks.len() != goal.stacks.len() {
+        return Err(PlanError::DifferentStacks { start: start.stacks.len(), goal: goal.stacks.len() });
+    }
+    if crate_counts(start) != crate_counts(goal) {
+        return Err(PlanError::DifferentCrates);
+    }
+
+    // Arrangements are numbered as they're found
+    let mut states: Vec<CrateStack> = vec![start.clone()];
+    let mut ids: HashMap<CrateStack, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut came_from: HashMap<usize, (usize, Instruction)> = HashMap::new();
+    let mut cost_so_far: HashMap<usize, i64> = HashMap::from([(0, 0)]);
+    let mut frontier = PriorityQueue::new();
+    frontier.push(0, -estimate(start, goal));
+
+    while let Some((current, _)) = frontier.pop() {
+        if states[current] == *goal {
+            return Ok(path(&came_from, current));
+        }
+        let cost = cost_so_far[&current] + 1;
+        for (next, instruction) in moves(&states[current], crane) {
+            let id = match ids.get(&next) {
+                Some(id) => *id,
+                None => {
+                    if states.len() == max_states {
+                        return Err(PlanError::TooManyStates { explored: states.len() });
+                    }
+                    ids.insert(next.clone(), states.len());
+                    states.push(next);
+                    states.len() - 1
+                }
+            };
+            if cost_so_far.get(&id).is_none_or(|c| cost < *c) {
+                cost_so_far.insert(id, cost);
+                // Negative, so the lowest estimate comes out first
+                frontier.push(id, -(cost + estimate(&states[id], goal)));
+                came_from.insert(id, (current, instruction));
+            }
+        }
+    }
+    Err(PlanError::Unreachable)
+}
+
+fn crate_counts(crate_stack: &CrateStack) -> HashMap<Crate, usize> {
+    let mut counts = HashMap::new();
+    crate_stack.stacks.iter().flatten().for_each(|c| *counts.entry(*c).or_insert(0) += 1);
+    counts
+}
+
+// Never more than the instructions left, see plan
+fn estimate(crate_stack: &CrateStack, goal: &CrateStack) -> i64 {
+    let (mut losing, mut gaining) = (0, 0);
+    for (stack, goal) in crate_stack.stacks.iter().zip(&goal.stacks) {
+        let same = stack.iter().zip(goal).take_while(|(a, b)| a == b).count();
+        losing += (stack.len() > same) as i64;
+        gaining += (goal.len() > same) as i64;
+    }
+    losing.max(gaining)
+}
+
+// Every instruction the crane could carry out next, and where it leads
+fn moves(crate_stack: &CrateStack, crane: &dyn Crane) -> Vec<(CrateStack, Instruction)> {
+    let stacks = crate_stack.stacks.len();
+    let mut moves = Vec::new();
+    for from in 0..stacks {
+        for to in (0..stacks).filter(|to| *to != from) {
+            for num in 1..=crate_stack.stacks[from].len() as u32 {
+                let instruction = Instruction { from: from as u32 + 1, to: to as u32 + 1, num };
+                let mut next = crate_stack.clone();
+                for size in lifts(crane, num) {
+                    next.lift(instruction.from, instruction.to, size);
+                }
+                moves.push((next, instruction));
+            }
+        }
+    }
+    moves
+}
+
+fn path(came_from: &HashMap<usize, (usize, Instruction)>, end: usize) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let mut current = end;
+    while let Some((previous, instruction)) = came_from.get(&current) {
+        instructions.push(instruction.clone());
+        current = *previous;
+    }
+    instructions.reverse();
+    instructions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        day05::{
+            crane::{CrateMover9000, CrateMover9001, Limited},
+            fixtures::{follow, stacks, TEST_INPUT},
+            render::render_instructions,
+            solution::{get_inputs, parse_instructions},
+        },
+        diagnostic::parse_all,
+    };
+
+    #[test]
+    fn test_plan_example() {
+        let (start, instructions) = get_inputs(TEST_INPUT);
+        for mut crane in [Box::new(CrateMover9000) as Box<dyn Crane>, Box::new(CrateMover9001)] {
+            let goal = follow(&start, &instructions, crane.as_mut());
+            let plan = plan(&start, &goal, crane.as_ref(), DEFAULT_MAX_STATES).unwrap();
+            // The puzzle's four instructions do it, a plan can't need more
+            assert!(plan.len() <= instructions.len(), "{:?}", plan);
+            assert_eq!(follow(&start, &plan, crane.as_mut()), goal);
+
+            // And it reads back in as instructions
+            assert_eq!(parse_all(&render_instructions(&plan), parse_instructions).unwrap(), plan);
+        }
+    }
+
+    #[test]
+    fn test_shortest() {
+        // The 9001 moves ABC across in one go, the 9000 turns it upside down so needs a
+        // second move to turn it back, and two at a time needs three
+        let start = stacks(&["ABC", "", ""]);
+        let goal = stacks(&["", "ABC", ""]);
+        assert_eq!(plan(&start, &goal, &CrateMover9001, DEFAULT_MAX_STATES).unwrap(), vec![Instruction { num: 3, from: 1, to: 2 }]);
+        assert_eq!(plan(&start, &goal, &CrateMover9000, DEFAULT_MAX_STATES).unwrap().len(), 2);
+        assert_eq!(plan(&start, &goal, &Limited { per_lift: 2 }, DEFAULT_MAX_STATES).unwrap().len(), 3);
+        assert_eq!(plan(&start, &start, &CrateMover9000, DEFAULT_MAX_STATES), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_estimate() {
+        let goal = stacks(&["AB", "C", ""]);
+        assert_eq!(estimate(&goal, &goal), 0);
+        // Stack 1 has B to lose, stack 2 needs it, and stack 3 has to lose C
+        assert_eq!(estimate(&stacks(&["A", "B", "C"]), &goal), 2);
+        assert_eq!(estimate(&stacks(&["ABC", "", ""]), &goal), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let start = stacks(&["AB", ""]);
+        assert_eq!(plan(&start, &stacks(&["A", "B", ""]), &CrateMover9000, 10), Err(PlanError::DifferentStacks { start: 2, goal: 3 }));
+        assert_eq!(plan(&start, &stacks(&["A", "C"]), &CrateMover9000, 10), Err(PlanError::DifferentCrates));
+        // With only one stack there's nowhere to move anything
+        assert_eq!(plan(&stacks(&["AB"]), &stacks(&["BA"]), &CrateMover9001, 10), Err(PlanError::Unreachable));
+        let start = stacks(&["ABCDEF", "", ""]);
+        assert_eq!(plan(&start, &stacks(&["", "", "ABCDEF"]), &CrateMover9000, 10), Err(PlanError::TooManyStates { explored: 10 }));
+    }
+}
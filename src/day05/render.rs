@@ -0,0 +1,134 @@
+use super::solution::{CrateStack, Instruction};
+
+// The stacks as the puzzle draws them, a line for each level from the top down, padded
+// out to every stack, then the stack numbers. A column is three wide, which has room for
+// the numbers up to 999. Past that every column is as wide as the widest number, which
+// parse_drawing can't read.
+pub fn drawing_lines(crate_stack: &CrateStack) -> Vec<String> {
+    let width = crate_stack.stacks.len().to_string().len().max(3);
+    let height = crate_stack.stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+    let mut lines: Vec<String> = (0..height)
+        .rev()
+        .map(|row| {
+            crate_stack
+                .stacks
+                .iter()
+                .map(|s| format!("{:^width$}", s.get(row).map(|c| format!("[{}]", c.0)).unwrap_or_default()))
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect();
+    let numbers: Vec<String> = (1..=crate_stack.stacks.len()).map(|n| format!("{:^width$}", n)).collect();
+    lines.push(numbers.join(" "));
+    lines
+}
+
+// The drawing as parse_drawing reads it. It needs a crate somewhere to have a line to parse.
+pub fn render(crate_stack: &CrateStack) -> String {
+    drawing_lines(crate_stack).iter().map(|l| format!("{}\n", l)).collect()
+}
+
+// A line for each instruction, as parse_instructions reads them
+pub fn render_instructions(instructions: &[Instruction]) -> String {
+    instructions.iter().map(|i| format!("{}\n", i)).collect()
+}
+
+// A whole puzzle input, the drawing then the instructions
+pub fn render_puzzle(crate_stack: &CrateStack, instructions: &[Instruction]) -> String {
+    format!("{}\n{}", render(crate_stack), render_instructions(instructions))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        day05::{
+            fixtures::TEST_INPUT,
+            solution::{get_inputs, parse_drawing, Crate},
+        },
+        diagnostic::parse_all,
+        rng::Rng,
+    };
+
+    // Up to twelve stacks of up to eight crates, with at least one crate somewhere
+    fn random_stacks(rng: &mut Rng) -> CrateStack {
+        loop {
+            let stacks: Vec<Vec<Crate>> = (0..1 + rng.below(12))
+                .map(|_| (0..rng.below(9)).map(|_| Crate((b'A' + rng.below(26) as u8) as char)).collect())
+                .collect();
+            if stacks.iter().any(|s| !s.is_empty()) {
+                return CrateStack { stacks };
+            }
+        }
+    }
+
+    fn parse(drawing: &str) -> CrateStack {
+        CrateStack::new_from_crates(parse_all(drawing, parse_drawing).unwrap_or_else(|e| panic!("{}\n{}", e, drawing)))
+    }
+
+    #[test]
+    fn test_render() {
+        let (crate_stack, instructions) = get_inputs(TEST_INPUT);
+        assert_eq!(render(&crate_stack), "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n");
+        assert_eq!(render_puzzle(&crate_stack, &instructions), format!("{}\n", TEST_INPUT));
+    }
+
+    #[test]
+    fn test_many_stacks() {
+        let mut crate_stack = CrateStack { stacks: vec![vec![]; 12] };
+        crate_stack.stacks[9].push(Crate('A'));
+        crate_stack.stacks[11].push(Crate('B'));
+        let lines = drawing_lines(&crate_stack);
+        assert_eq!(lines[0], format!("{}[A]     [B]", " ".repeat(36)));
+        assert_eq!(lines[1], " 1   2   3   4   5   6   7   8   9  10  11  12 ");
+        assert_eq!(parse(&render(&crate_stack)), crate_stack);
+
+        // Four digit numbers need wider columns
+        let mut crate_stack = CrateStack { stacks: vec![vec![]; 1000] };
+        crate_stack.stacks[999].push(Crate('C'));
+        let lines = drawing_lines(&crate_stack);
+        assert!(lines[0].ends_with(" [C] "));
+        assert!(lines[1].starts_with(" 1    2    3  ") && lines[1].ends_with(" 998  999  1000"));
+        assert_eq!(lines[0].len(), lines[1].len());
+    }
+
+    #[test]
+    fn test_parse_then_render() {
+        let mut rng = Rng::new(5);
+        for _ in 0..500 {
+            let drawing = render(&random_stacks(&mut rng));
+            assert_eq!(render(&parse(&drawing)), drawing);
+        }
+    }
+
+    #[test]
+    fn test_render_then_parse() {
+        let mut rng = Rng::new(2022);
+        for _ in 0..500 {
+            let crate_stack = random_stacks(&mut rng);
+            assert_eq!(parse(&render(&crate_stack)), crate_stack);
+        }
+    }
+
+    #[test]
+    fn test_puzzle_round_trip() {
+        let mut rng = Rng::new(9);
+        for _ in 0..100 {
+            let crate_stack = random_stacks(&mut rng);
+            // Each instruction moves some of whatever is on the tallest stack
+            let mut heights: Vec<usize> = crate_stack.stacks.iter().map(Vec::len).collect();
+            let instructions: Vec<Instruction> = (0..1 + rng.below(10))
+                .map(|_| {
+                    let from = (0..heights.len()).max_by_key(|s| heights[*s]).unwrap();
+                    let to = rng.below(heights.len());
+                    let num = 1 + rng.below(heights[from]);
+                    heights[from] -= num;
+                    heights[to] += num;
+                    Instruction { from: from as u32 + 1, to: to as u32 + 1, num: num as u32 }
+                })
+                .collect();
+            let input = render_puzzle(&crate_stack, &instructions);
+            assert_eq!(get_inputs(&input), (crate_stack, instructions));
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use super::{
+    crane::{lifts, Crane},
+    solution::{parse_inputs, CrateStack, Instruction},
+};
+
+// Why the stacks can't be the result of following the instructions
+#[derive(Debug, PartialEq)]
+pub enum ReverseError {
+    // The lines count from the first instruction, like validate's
+    NoSuchStack { line: usize, stack: u32, stacks: usize },
+    TooFewCrates { line: usize, stack: u32, crates: usize, moved: u32 },
+}
+
+impl fmt::Display for ReverseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSuchStack { line, stack, stacks } => write!(f, "line {}: there's no stack {}, they're numbered 1 to {}", line, stack, stacks),
+            Self::TooFewCrates { line, stack, crates, moved } => {
+                write!(f, "line {}: {} crates were moved onto stack {}, but it only has {} afterwards", line, moved, stack, crates)
+            }
+        }
+    }
+}
+
+// Work out how the stacks started, from how they ended up. Each instruction is undone by
+// making the crane's lifts in reverse, taking each one back off the stack it went to.
+pub fn reverse(end: &CrateStack, instructions: &[Instruction], crane: &dyn Crane) -> Result<CrateStack, ReverseError> {
+    let mut crate_stack = end.clone();
+    let stacks = crate_stack.stacks.len();
+    for (idx, i) in instructions.iter().enumerate().rev() {
+        let line = idx + 1;
+        for stack in [i.from, i.to] {
+            if stack == 0 || stack as usize > stacks {
+                return Err(ReverseError::NoSuchStack { line, stack, stacks });
+            }
+        }
+        let crates = crate_stack.stacks[(i.to - 1) as usize].len();
+        if crates < i.num as usize {
+            return Err(ReverseError::TooFewCrates { line, stack: i.to, crates, moved: i.num });
+        }
+        for size in lifts(crane, i.num).into_iter().rev() {
+            crate_stack.lift(i.to, i.from, size);
+        }
+    }
+    Ok(crate_stack)
+}
+
+// The same as reverse, for a puzzle input whose drawing shows how the stacks ended up
+pub fn reverse_input(input: &str, crane: &dyn Crane) -> Result<CrateStack, String> {
+    let (end, instructions, lines_before) = parse_inputs(input).map_err(|e| e.to_string())?;
+    reverse(&end, &instructions, crane).map_err(|e| match e {
+        ReverseError::NoSuchStack { line, stack, stacks } => ReverseError::NoSuchStack { line: line + lines_before, stack, stacks },
+        ReverseError::TooFewCrates { line, stack, crates, moved } => {
+            ReverseError::TooFewCrates { line: line + lines_before, stack, crates, moved }
+        }
+    }
+    .to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        day05::{
+            crane::{CrateMover9000, CrateMover9001, Limited},
+            fixtures::{follow, TEST_INPUT},
+            solution::{get_inputs, Crate},
+        },
+        rng::Rng,
+    };
+
+    #[test]
+    fn test_reverse() {
+        let (start, instructions) = get_inputs(TEST_INPUT);
+        let cranes: [Box<dyn Crane>; 4] =
+            [Box::new(CrateMover9000), Box::new(CrateMover9001), Box::new(Limited { per_lift: 2 }), Box::new(Limited { per_lift: 1 })];
+        for mut crane in cranes {
+            let end = follow(&start, &instructions, crane.as_mut());
+            assert_eq!(reverse(&end, &instructions, crane.as_ref()), Ok(start.clone()), "{}", crane.name());
+        }
+    }
+
+    #[test]
+    fn test_reverse_input() {
+        // The example ends like this with the CrateMover 9001
+        let end = "        [D]\n        [N]\n        [Z]\n[M] [C] [P]\n 1   2   3 \n\n";
+        let instructions = "move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
+        let start = reverse_input(&format!("{}{}", end, instructions), &CrateMover9001).unwrap();
+        assert_eq!(start, get_inputs(TEST_INPUT).0);
+    }
+
+    #[test]
+    fn test_random_round_trips() {
+        let mut rng = Rng::new(49);
+        for _ in 0..200 {
+            let stacks: Vec<Vec<Crate>> =
+                (0..2 + rng.below(6)).map(|_| (0..rng.below(6)).map(|_| Crate((b'A' + rng.below(26) as u8) as char)).collect()).collect();
+            let start = CrateStack { stacks };
+            let mut heights: Vec<usize> = start.stacks.iter().map(Vec::len).collect();
+            let mut instructions = Vec::new();
+            for _ in 0..20 {
+                let from = rng.below(heights.len());
+                let to = rng.below(heights.len());
+                if heights[from] > 0 {
+                    let num = 1 + rng.below(heights[from]);
+                    heights[from] -= num;
+                    heights[to] += num;
+                    instructions.push(Instruction { from: from as u32 + 1, to: to as u32 + 1, num: num as u32 });
+                }
+            }
+            let mut crane = Limited { per_lift: 1 + rng.below(4) as u32 };
+            let end = follow(&start, &instructions, &mut crane);
+            assert_eq!(reverse(&end, &instructions, &crane), Ok(start));
+        }
+    }
+
+    #[test]
+    fn test_inconsistent() {
+        let (start, instructions) = get_inputs(TEST_INPUT);
+        // Treating the start as the end, the last two instructions undo, but the second wants
+        // three crates back off stack 3 and there's only P
+        let error = reverse(&start, &instructions, &CrateMover9000);
+        assert_eq!(error, Err(ReverseError::TooFewCrates { line: 2, stack: 3, crates: 1, moved: 3 }));
+        assert_eq!(error.unwrap_err().to_string(), "line 2: 3 crates were moved onto stack 3, but it only has 1 afterwards");
+
+        let error = reverse(&start, &[Instruction { num: 1, from: 4, to: 1 }], &CrateMover9001);
+        assert_eq!(error, Err(ReverseError::NoSuchStack { line: 1, stack: 4, stacks: 3 }));
+        // The lines count through the whole input
+        assert_eq!(
+            reverse_input(TEST_INPUT, &CrateMover9000),
+            Err("line 7: 3 crates were moved onto stack 3, but it only has 1 afterwards".to_string())
+        );
+    }
+}
@@ -1,48 +1,122 @@
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_till},
+    bytes::complete::take_till,
     character::{
         complete::{anychar, char, digit1},
     },
     combinator::{map_res, opt},
+    error::context,
     multi::{many1, separated_list1},
     sequence::{delimited, tuple},
-    IResult
 };
 
-const INPUT: &str = include_str!("input.txt");
+use std::fmt;
+
+use super::crane::{Crane, CrateMover9000, CrateMover9001};
+use crate::diagnostic::{parse_all, separated_list1_strict, tag, Diagnostic, IResult};
+
+pub const INPUT: &str = include_str!("input.txt");
 
 pub fn solution() {
-    println!("Solution for day five part one = {}", part1());
-    println!("Solution for day five part two = {}", part2());
+    println!("Solution for day five part one = {}", part1(INPUT));
+    println!("Solution for day five part two = {}", part2(INPUT));
 }
 
-fn get_inputs() -> (CrateStack, Vec<Instruction>) {
-  let (_, (crates, instructions)) = parse_puzzle_input(INPUT).unwrap();
-  let mut crate_stack = CrateStack::new_from_crates(crates);
+// Panics if the input doesn't parse, or an instruction can't be carried out
+pub fn get_inputs(input: &str) -> (CrateStack, Vec<Instruction>) {
+  let (crate_stack, instructions, lines_before) = parse_inputs(input).unwrap_or_else(|e| panic!("{}", e));
+  if let Err(e) = validate(&crate_stack, &instructions) {
+    panic!("{}", InstructionError { line: e.line + lines_before, ..e });
+  }
   (crate_stack, instructions)
 }
 
-fn part1() -> String {
-  let (mut crate_stack, instructions) = get_inputs();
-  instructions.iter().fold(&mut crate_stack, |acc, i| {
-    acc.process_instruction_part1(i);
-    acc
-  });
-  crate_stack.get_top_crates()
+// The stacks and the instructions, without checking the instructions can be carried out.
+// Also how many lines come before the instructions, to add to a line counted from the
+// first instruction to make it a line of the whole input.
+pub fn parse_inputs(input: &str) -> Result<(CrateStack, Vec<Instruction>, usize), Diagnostic> {
+  let (crates, instructions) = parse_all(input, parse_puzzle_input)?;
+  // The drawing, the line of stack numbers and a blank line come before the instructions
+  let lines_before = crates.len() + 2;
+  Ok((CrateStack::new_from_crates(crates), instructions, lines_before))
+}
+
+pub fn part1(input: &str) -> String {
+  run(input, &mut CrateMover9000)
+}
+
+pub fn part2(input: &str) -> String {
+  run(input, &mut CrateMover9001)
 }
 
-fn part2() -> String {
-  let (mut crate_stack, instructions) = get_inputs();
-  instructions.iter().fold(&mut crate_stack, |acc, i| {
-    acc.process_instruction_part2(i);
-    acc
-  });
+// Follow every instruction with the crane, and read off the top crates
+pub fn run(input: &str, crane: &mut dyn Crane) -> String {
+  let (mut crate_stack, instructions) = get_inputs(input);
+  for i in &instructions {
+    crate_stack.apply(crane, i).unwrap_or_else(|e| panic!("{}", e));
+  }
   crate_stack.get_top_crates()
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
-struct Crate(char);
+#[derive(Debug, PartialEq)]
+pub enum MoveError {
+  // Stacks are numbered from 1
+  NoSuchStack { stack: u32, stacks: usize },
+  NotEnoughCrates { stack: u32, crates: usize, wanted: u32 },
+}
+
+impl fmt::Display for MoveError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::NoSuchStack { stack, stacks } => write!(f, "there's no stack {}, they're numbered 1 to {}", stack, stacks),
+      Self::NotEnoughCrates { stack, crates, wanted } => {
+        write!(f, "stack {} has {} crates, {} can't be moved from it", stack, crates, wanted)
+      }
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InstructionError {
+  // Counting from the first instruction, get_inputs makes it a line of the whole input
+  pub line: usize,
+  pub error: MoveError,
+}
+
+impl fmt::Display for InstructionError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "line {}: {}", self.line, self.error)
+  }
+}
+
+// A dry run of the instructions that only keeps track of how tall the stacks are. Every
+// crane moves the same number of crates, so they all fail at the same instruction.
+pub fn validate(crate_stack: &CrateStack, instructions: &[Instruction]) -> Result<(), InstructionError> {
+  let mut heights: Vec<usize> = crate_stack.stacks.iter().map(Vec::len).collect();
+  for (idx, i) in instructions.iter().enumerate() {
+    check(&heights, |h| *h, i).map_err(|error| InstructionError { line: idx + 1, error })?;
+    heights[(i.from-1) as usize] -= i.num as usize;
+    heights[(i.to-1) as usize] += i.num as usize;
+  }
+  Ok(())
+}
+
+// Takes either the stacks or just their heights
+fn check<T>(stacks: &[T], height: impl Fn(&T) -> usize, instruction: &Instruction) -> Result<(), MoveError> {
+  for stack in [instruction.from, instruction.to] {
+    if stack == 0 || stack as usize > stacks.len() {
+      return Err(MoveError::NoSuchStack { stack, stacks: stacks.len() });
+    }
+  }
+  let crates = height(&stacks[(instruction.from-1) as usize]);
+  if crates < instruction.num as usize {
+    return Err(MoveError::NotEnoughCrates { stack: instruction.from, crates, wanted: instruction.num });
+  }
+  Ok(())
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Crate(pub char);
 
 impl From<&str> for Crate {
     fn from(s: &str) -> Self {
@@ -52,46 +126,59 @@ impl From<&str> for Crate {
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Instruction {
+// The rows of crates as they appear in the drawing, top row first
+pub type CrateRows = Vec<Vec<Option<Crate>>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
     pub from: u32,
     pub to: u32,
     pub num: u32,
 }
 
-#[derive(Debug)]
-struct CrateStack {
+// The way the puzzle writes it, move 1 from 2 to 1
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.num, self.from, self.to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CrateStack {
     pub stacks: Vec<Vec<Crate>>,
 }
 
 impl CrateStack {
-    pub fn process_instruction_part1(&mut self, instruction: &Instruction) {
-      for _ in 0..instruction.num {
-        let c = self.stacks[(instruction.from-1) as usize].pop().unwrap();
-        self.stacks[(instruction.to-1) as usize].push(c);
-      }
-    } 
-    
-    pub fn process_instruction_part2(&mut self, instruction: &Instruction) {
-      let mut temp = Vec::with_capacity(instruction.num as usize);
-      for _ in 0..instruction.num {
-        let c = self.stacks[(instruction.from-1) as usize].pop().unwrap();
-        temp.push(c);
+    // Move the crates for the instruction, in as many lifts as the crane needs. Nothing
+    // moves if the instruction can't be carried out.
+    pub fn apply(&mut self, crane: &mut dyn Crane, instruction: &Instruction) -> Result<(), MoveError> {
+      check(&self.stacks, Vec::len, instruction)?;
+      let mut remaining = instruction.num;
+      while remaining > 0 {
+        let size = crane.lift_size(remaining).clamp(1, remaining);
+        self.lift(instruction.from, instruction.to, size);
+        crane.lifted(size);
+        remaining -= size;
       }
+      Ok(())
+    }
 
-      for c in temp.iter().rev() {
-          self.stacks[(instruction.to-1) as usize].push(*c);
-      }
+    // Take crates off the top of one stack and put them on another, keeping their order.
+    // Panics if the stacks aren't there or there aren't enough crates, apply checks first.
+    pub fn lift(&mut self, from: u32, to: u32, num: u32) {
+      let from = &mut self.stacks[(from-1) as usize];
+      let crates: Vec<Crate> = from.drain(from.len() - num as usize..).collect();
+      self.stacks[(to-1) as usize].extend(crates);
     }
 
-    pub fn new_from_crates(crates: Vec<Vec<Option<Crate>>>) -> Self {
+    pub fn new_from_crates(crates: CrateRows) -> Self {
       assert!(!crates.is_empty());
       let num_stacks = crates.len();
       let stack_depth = crates[0].len();
-      let mut stacks: Vec<Vec<Crate>> = Vec::with_capacity(num_stacks as usize);
+      let mut stacks: Vec<Vec<Crate>> = Vec::with_capacity(num_stacks);
 
       // Prime the stacks
-      crates[0].iter().for_each(|_| stacks.push(Vec::with_capacity(stack_depth as usize)));
+      crates[0].iter().for_each(|_| stacks.push(Vec::with_capacity(stack_depth)));
 
       //Start pushing the crates onto the stack
       for row in crates.iter().rev() {
@@ -105,8 +192,9 @@ impl CrateStack {
       Self { stacks }
     }
 
+    // A space for an empty stack, so the rest stay in line
     pub fn get_top_crates(&self) -> String {
-      self.stacks.iter().map(|s| s[s.len()-1].0).collect()
+      self.stacks.iter().map(|s| s.last().map_or(' ', |c| c.0)).collect()
     }
 }
 
@@ -133,7 +221,7 @@ fn parse_single_crate_line(input: &str) -> IResult<&str, Vec<Option<Crate>>> {
 }
 
 // Parse all crates in the input
-fn parse_all_crates(input: &str) -> IResult<&str, Vec<Vec<Option<Crate>>>> {
+fn parse_all_crates(input: &str) -> IResult<&str, CrateRows> {
     separated_list1(char('\n'), parse_single_crate_line)(input)
 }
 
@@ -144,39 +232,46 @@ fn decimal(input: &str) -> IResult<&str, u32> {
 
 // Parse instruction
 fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
-    tuple((
+    context("parse_instruction", tuple((
         tag("move "),
         decimal,
         tag(" from "),
         decimal,
         tag(" to "),
         decimal,
-    ))(input)
+    )))(input)
     .map(|(input, (_, num, _, from, _, to))| (input, Instruction { from, to, num }))
 }
 
 // Parse all of the instructions
-fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
-  separated_list1(char('\n'), parse_instruction)(input)
+pub fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+  separated_list1_strict(char('\n'), parse_instruction)(input)
 }
 
 // Parse the useless line with numbers, i.e. ' 1  2  3  4 etc'
 fn parse_number_line(input: &str) -> IResult<&str, ()> {
-  tuple((
+  context("parse_number_line", tuple((
     take_till(|c| c == '\n'),
     tag("\n")
-  ))(input).map(|(i, _)| (i, ()))
+  )))(input).map(|(i, _)| (i, ()))
 }
 
-// Parse the puzzle input into a 2d vector of crates, and a list of instructions
-fn parse_puzzle_input(input: &str) -> IResult<&str, (Vec<Vec<Option<Crate>>>, Vec<Instruction>)> {
+// Parse the drawing of the crates, down to and including the line of numbers
+pub fn parse_drawing(input: &str) -> IResult<&str, CrateRows> {
   tuple((
     parse_all_crates,
     tag("\n"),
     parse_number_line,
+  ))(input).map(|(i, (crates, _, _))| (i, crates))
+}
+
+// Parse the puzzle input into a 2d vector of crates, and a list of instructions
+pub fn parse_puzzle_input(input: &str) -> IResult<&str, (CrateRows, Vec<Instruction>)> {
+  context("parse_puzzle_input", tuple((
+    parse_drawing,
     char('\n'),
     parse_instructions
-  ))(input).map(|(i, (crates, _, _, _, instructions))| {
+  )))(input).map(|(i, (crates, _, instructions))| {
     (i, (crates, instructions))
   })
 }
@@ -184,16 +279,7 @@ fn parse_puzzle_input(input: &str) -> IResult<&str, (Vec<Vec<Option<Crate>>>, Ve
 #[cfg(test)]
 mod test {
     use super::*;
-
-    const TEST_INPUT: &str = "    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
+    use crate::day05::fixtures::TEST_INPUT;
 
     #[test]
     fn test_crate_parser() {
@@ -335,7 +421,7 @@ move 9 from 88 to 2";
       ];
 
       for i in instructions {
-        crate_stack.process_instruction_part1(&i);
+        crate_stack.apply(&mut CrateMover9000, &i).unwrap();
       }
       println!("{:?}", crate_stack);
       assert_eq!(crate_stack.get_top_crates(), "CMZ");
@@ -358,10 +444,57 @@ move 9 from 88 to 2";
       ];
 
       for i in instructions {
-        crate_stack.process_instruction_part2(&i);
+        crate_stack.apply(&mut CrateMover9001, &i).unwrap();
       }
       println!("{:?}", crate_stack);
       assert_eq!(crate_stack.get_top_crates(), "MCD");
     }
 
+    #[test]
+    fn test_validate() {
+      let (crate_stack, _) = get_inputs(TEST_INPUT);
+      let instructions = vec![
+        Instruction{num: 1, from: 2, to: 1},
+        Instruction{num: 4, from: 2, to: 3},
+        Instruction{num: 1, from: 0, to: 1},
+      ];
+      assert_eq!(validate(&crate_stack, &instructions[..1]), Ok(()));
+      // Stack 2 only has two crates left after the first instruction
+      assert_eq!(
+        validate(&crate_stack, &instructions),
+        Err(InstructionError { line: 2, error: MoveError::NotEnoughCrates { stack: 2, crates: 2, wanted: 4 } })
+      );
+      assert_eq!(
+        validate(&crate_stack, &[Instruction{num: 1, from: 1, to: 4}]),
+        Err(InstructionError { line: 1, error: MoveError::NoSuchStack { stack: 4, stacks: 3 } })
+      );
+      assert_eq!(
+        validate(&crate_stack, &instructions[2..]).unwrap_err().error,
+        MoveError::NoSuchStack { stack: 0, stacks: 3 }
+      );
+    }
+
+    #[test]
+    fn test_apply_errors() {
+      let (mut crate_stack, _) = get_inputs(TEST_INPUT);
+      let error = crate_stack.apply(&mut CrateMover9001, &Instruction{num: 3, from: 3, to: 1});
+      assert_eq!(error, Err(MoveError::NotEnoughCrates { stack: 3, crates: 1, wanted: 3 }));
+      // Nothing moved
+      assert_eq!(crate_stack.get_top_crates(), "NDP");
+
+      crate_stack.apply(&mut CrateMover9001, &Instruction{num: 1, from: 3, to: 1}).unwrap();
+      assert_eq!(crate_stack.get_top_crates(), "PD ");
+    }
+
+    #[test]
+    #[should_panic(expected = "line 7: stack 2 has 2 crates, 5 can't be moved from it")]
+    fn test_bad_instruction_line() {
+      get_inputs(&TEST_INPUT.replace("move 3 from 1 to 3", "move 5 from 2 to 3"));
+    }
+
+    #[test]
+    fn test_answers() {
+      assert_eq!(part1(INPUT), "CFFHVVHNC");
+      assert_eq!(part2(INPUT), "FSZWBPTBG");
+    }
 }
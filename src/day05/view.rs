@@ -0,0 +1,155 @@
+use crate::explorer::{Key, View};
+
+use super::{
+    crane::{Crane, CrateMover9000, CrateMover9001},
+    history::History,
+    render::drawing_lines,
+    solution::{get_inputs, CrateStack, Instruction},
+};
+
+// Step through the instructions, one at a time, and watch the stacks change
+pub struct StacksView {
+    start: CrateStack,
+    history: History,
+    crate_mover_9001: bool,
+    // The stack picked to move a crate from by hand, waiting for the one to move it to
+    moving_from: Option<u32>,
+    // Why the last move by hand couldn't be made
+    error: Option<String>,
+}
+
+impl StacksView {
+    pub fn new(input: &str) -> Self {
+        // Checks the instructions too, so that none of them fail part way through
+        let (start, instructions) = get_inputs(input);
+        let history = History::new(start.clone(), instructions, Box::new(CrateMover9000));
+        Self { start, history, crate_mover_9001: false, moving_from: None, error: None }
+    }
+
+    fn seek(&mut self, step: usize) {
+        self.history.seek(step).expect("The instructions were checked when they were loaded");
+    }
+
+    // Start again with the other crane, at the same instruction
+    fn switch_crane(&mut self) {
+        self.crate_mover_9001 = !self.crate_mover_9001;
+        let crane: Box<dyn Crane> = if self.crate_mover_9001 { Box::new(CrateMover9001) } else { Box::new(CrateMover9000) };
+        let step = self.history.position();
+        self.history = History::new(self.start.clone(), self.history.instructions().to_vec(), crane);
+        self.seek(step);
+    }
+
+    // The first stack picked is where the crate comes from, the second where it goes
+    fn pick_stack(&mut self, stack: u32) {
+        let Some(from) = self.moving_from.take() else {
+            self.moving_from = Some(stack);
+            return;
+        };
+        let instruction = Instruction { num: 1, from, to: stack };
+        self.error = self.history.apply(instruction).err().map(|e| e.to_string());
+    }
+}
+
+impl View for StacksView {
+    fn title(&self) -> String {
+        let crane = if self.crate_mover_9001 { "CrateMover 9001" } else { "CrateMover 9000" };
+        format!(
+            "Day 5 - {} - instruction {}/{} - top crates {}",
+            crane,
+            self.history.position(),
+            self.history.instructions().len(),
+            self.history.stacks().get_top_crates()
+        )
+    }
+
+    fn help(&self) -> &'static str {
+        "right/left: next/previous instruction  pgup/pgdn: 10 instructions  g/G: first/last  c: switch crane  1-9 1-9: move a crate from one stack to another"
+    }
+
+    fn render(&self, _width: usize, height: usize) -> Vec<String> {
+        let mut lines = match self.history.diff(self.history.position()) {
+            None => vec!["Starting arrangement".to_string(), String::new()],
+            Some(diff) => vec![format!("After: {}", diff.instruction), format!("Changed: {}", diff.describe_changes())],
+        };
+        if let Some(from) = self.moving_from {
+            lines[1] = format!("Moving a crate from stack {}, pick the stack to move it to", from);
+        } else if let Some(error) = &self.error {
+            lines[1] = format!("Can't move it, {}", error);
+        }
+
+        // Keep the bottom of the stacks and the numbers in view when the stacks are tall
+        let stacks = drawing_lines(self.history.stacks());
+        let skip = stacks.len().saturating_sub(height.saturating_sub(lines.len()));
+        lines.extend(stacks.into_iter().skip(skip));
+        lines
+    }
+
+    fn handle_key(&mut self, key: Key) {
+        if !matches!(key, Key::Char('1'..='9')) {
+            self.moving_from = None;
+        }
+        self.error = None;
+        match key {
+            Key::Right | Key::Char('n') => self.seek(self.history.position() + 1),
+            Key::Left | Key::Char('p') => self.seek(self.history.position().saturating_sub(1)),
+            Key::PageDown => self.seek(self.history.position() + 10),
+            Key::PageUp => self.seek(self.history.position().saturating_sub(10)),
+            Key::Char('g') => self.seek(0),
+            Key::Char('G') => self.seek(self.history.instructions().len()),
+            Key::Char('c') => self.switch_crane(),
+            Key::Char(c @ '1'..='9') => self.pick_stack(c.to_digit(10).unwrap()),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day05::fixtures::TEST_INPUT;
+
+    #[test]
+    fn test_step_through() {
+        let mut view = StacksView::new(TEST_INPUT);
+        assert_eq!(view.render(80, 20)[2..], ["    [D]    ", "[N] [C]    ", "[Z] [M] [P]", " 1   2   3 "]);
+
+        view.handle_key(Key::Char('G'));
+        assert_eq!(view.history.stacks().get_top_crates(), "CMZ");
+        view.handle_key(Key::Char('c'));
+        assert_eq!(view.history.stacks().get_top_crates(), "MCD");
+
+        view.handle_key(Key::Left);
+        let lines = view.render(80, 20);
+        assert_eq!(lines[0], "After: move 2 from 2 to 1");
+        assert_eq!(lines[1], "Changed: stack 2 -MC, stack 1 +MC");
+        assert!(view.title().contains("instruction 3/4"));
+    }
+
+    #[test]
+    fn test_move_by_hand() {
+        let mut view = StacksView::new(TEST_INPUT);
+        view.handle_key(Key::Right);
+        view.handle_key(Key::Char('3'));
+        assert_eq!(view.render(80, 20)[1], "Moving a crate from stack 3, pick the stack to move it to");
+        view.handle_key(Key::Char('2'));
+        let lines = view.render(80, 20);
+        assert_eq!(lines[0], "After: move 1 from 3 to 2");
+        assert_eq!(lines[2..], ["[D] [P]    ", "[N] [C]    ", "[Z] [M]    ", " 1   2   3 "]);
+        // The puzzle's instructions after the first have gone
+        assert!(view.title().contains("instruction 2/2"));
+
+        view.handle_key(Key::Char('3'));
+        view.handle_key(Key::Char('3'));
+        assert_eq!(view.render(80, 20)[1], "Can't move it, stack 3 has 0 crates, 1 can't be moved from it");
+        // Undoing still works through the moves made by hand
+        view.handle_key(Key::Left);
+        assert_eq!(view.render(80, 20)[0], "After: move 1 from 2 to 1");
+    }
+
+    #[test]
+    fn test_render_keeps_bottom_in_view() {
+        let view = StacksView::new(TEST_INPUT);
+        let lines = view.render(80, 4);
+        assert_eq!(lines[2..], ["[Z] [M] [P]", " 1   2   3 "]);
+    }
+}
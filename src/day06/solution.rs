@@ -1,10 +1,18 @@
 use std::collections::HashSet;
 
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
 
 pub fn solution() {
-  println!("Solution for day six part one = {}", find_marker(INPUT.trim(), 4));
-  println!("Solution for day six part two = {}", find_marker(INPUT.trim(), 14));
+  println!("Solution for day six part one = {}", part1(INPUT));
+  println!("Solution for day six part two = {}", part2(INPUT));
+}
+
+pub fn part1(input: &str) -> u32 {
+  find_marker(input.trim(), 4)
+}
+
+pub fn part2(input: &str) -> u32 {
+  find_marker(input.trim(), 14)
 }
 
 fn find_marker(s: &str, marker_len: u32) -> u32 {
@@ -57,4 +65,9 @@ mod test {
     }
   }
 
-}
\ No newline at end of file
+  #[test]
+  fn test_answers() {
+    assert_eq!(part1(INPUT), 1702);
+    assert_eq!(part2(INPUT), 3559);
+  }
+}
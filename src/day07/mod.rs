@@ -1 +1,2 @@
-pub mod solution;
\ No newline at end of file
+pub mod solution;
+pub mod view;
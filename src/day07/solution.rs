@@ -1,37 +1,49 @@
 use std::cell::RefCell;
-use nom::{IResult, bytes::complete::{tag, take_while}, sequence::tuple, character::complete::{alpha1, char}};
+use nom::{bytes::complete::take_while, sequence::tuple, character::complete::{alpha1, char}};
 use nom::branch::alt;
 use nom::combinator::map_res;
 use nom::character::complete::digit1;
-use nom::multi::separated_list1;
+use nom::error::context;
 use std::rc::Rc;
 
+use crate::diagnostic::{parse_all, separated_list1_strict, tag, IResult};
 
-const INPUT: &str = include_str!("input.txt");
+
+pub const INPUT: &str = include_str!("input.txt");
 
 pub fn solution() {
-  // Part 1
-  let (_, lines) = parse_input(INPUT).unwrap();
-  let fs = build_fs(lines);
+  println!("Solution for day seven part one = {}", part1(INPUT)); 
+  println!("Solution for day seven part two = {}", part2(INPUT)); 
+}
+
+pub fn get_fs(input: &str) -> Rc<RefCell<Dir>> {
+  let lines = parse_all(input, parse_input).unwrap_or_else(|e| panic!("{}", e));
+  build_fs(lines)
+}
+
+pub fn part1(input: &str) -> u32 {
+  let fs = get_fs(input);
   let mut dirs = Vec::new();
   gather_dirs(&fs.borrow(), &mut dirs);
-  let part1_answer: u32 = dirs.iter().cloned().filter(|n| *n<=100000).sum();
-  println!("Solution for day seven part one = {}", part1_answer); 
-
+  dirs.iter().cloned().filter(|n| *n<=100000).sum()
+}
 
-  // Part 2
+pub fn part2(input: &str) -> u32 {
+  let fs = get_fs(input);
+  let mut dirs = Vec::new();
+  gather_dirs(&fs.borrow(), &mut dirs);
   let space_used = fs.borrow().get_size();
   let unused_space = 70000000 - space_used;
   let target = 30000000;
   let mut candidates_for_deletion: Vec<u32> = dirs.iter().cloned().filter(|n| (unused_space+n) >= target).collect();
   candidates_for_deletion.sort();
-  let part2_answer = candidates_for_deletion[0];
-  println!("Solution for day seven part two = {}", part2_answer); 
+  candidates_for_deletion[0]
 }
 
 // A struct to hold a line of input 
+#[allow(clippy::enum_variant_names)]
 #[derive(PartialEq, Debug)]
-enum Line {
+pub enum Line {
   Cd(String),
   Ls,
   FileLine(File),
@@ -39,7 +51,7 @@ enum Line {
 }
 
 #[derive(PartialEq, Debug)]
-struct File {
+pub struct File {
   pub name: String,
   pub size: u32,
 }
@@ -48,7 +60,7 @@ struct File {
 // RefCells inside, which allow interior mutability without needing to declare a var
 // as 'mut'. This is moving compile time safety checks to runtime.
 #[derive(PartialEq, Debug)]
-struct Dir {
+pub struct Dir {
   pub name: String,
   pub files: Vec<Rc<RefCell<File>>>,
   pub dirs: Vec<Rc<RefCell<Dir>>>,
@@ -74,27 +86,27 @@ fn is_valid_path_char(c: char) -> bool {
 
 // Parse the 'cd' command
 fn parse_cd(input: &str) -> IResult<&str, Line> {
-  tuple((
+  context("parse_cd", tuple((
     tag("$ cd "),
     take_while(is_valid_path_char)
-  ))(input).map(|(i,(_, o))| {
+  )))(input).map(|(i,(_, o))| {
     (i, Line::Cd(o.to_owned()))
   })
 }
 
 // Parse the ls command
 fn parse_ls(input: &str) -> IResult<&str, Line> {
-  tag("$ ls")(input).map(|(i,_o)| {
+  context("parse_ls", tag("$ ls"))(input).map(|(i,_o)| {
     (i, Line::Ls)
   })
 }
 
 // Parse the dir entry
 fn parse_dir(input: &str) -> IResult<&str, Line> {
-  tuple((
+  context("parse_dir", tuple((
     tag("dir "),
     alpha1
-  ))(input).map(|(i,(_, o))| {
+  )))(input).map(|(i,(_, o))| {
     let d = Dir { name: o.to_string(), files: Vec::new(), dirs: Vec::new(), parent: None };
     (i, Line::DirLine(d))
   })
@@ -107,11 +119,11 @@ fn decimal(input: &str) -> IResult<&str, u32> {
 
 // Parse the file
 fn parse_file(input: &str) -> IResult<&str, Line> {
-  tuple((
+  context("parse_file", tuple((
     decimal,
     tag(" "),
     take_while(is_valid_path_char)
-  ))(input).map(|(i,(size, _, name))| {
+  )))(input).map(|(i,(size, _, name))| {
     let f = File { name: name.to_string(), size };
     (i, Line::FileLine(f))
   })
@@ -119,17 +131,17 @@ fn parse_file(input: &str) -> IResult<&str, Line> {
 
 // Parse a line from today's challange
 fn parse_line(input: &str) -> IResult<&str, Line> {
-  alt((
+  context("parse_line", alt((
     parse_cd,
     parse_ls,
     parse_dir,
     parse_file
-  ))(input)
+  )))(input)
 }
 
 // Parse all of today's input
-fn parse_input(input: &str) -> IResult<&str, Vec<Line>> {
-  separated_list1(char('\n'), parse_line)(input)
+pub fn parse_input(input: &str) -> IResult<&str, Vec<Line>> {
+  separated_list1_strict(char('\n'), parse_line)(input)
 }
 
 // Traverse the 'filesystem' and gather the size of all dirs as a Vec
@@ -140,7 +152,7 @@ fn gather_dirs(dir: &Dir, dirs: &mut Vec<u32>) {
   }
 }
 
-fn build_fs(lines: Vec<Line>) -> Rc<RefCell<Dir>> {
+pub fn build_fs(lines: Vec<Line>) -> Rc<RefCell<Dir>> {
   let root = Rc::new(RefCell::new(Dir { name: "/".to_string(), files: Vec::new(), dirs: Vec::new(), parent: None }));
   let mut current_dir: Rc<RefCell<Dir>> = Rc::clone(&root);
 
@@ -257,10 +269,13 @@ dir e
       Line::FileLine(File { name: "f".to_string(), size: 29116 }),
     ];
 
-    let (_, lines) = parse_input(input).unwrap();
+    let lines = parse_all(input, parse_input).unwrap_or_else(|e| panic!("{}", e));
     assert_eq!(lines, expected);
   }
 
-
-
-}
\ No newline at end of file
+  #[test]
+  fn test_answers() {
+    assert_eq!(part1(INPUT), 1306611);
+    assert_eq!(part2(INPUT), 13210366);
+  }
+}
@@ -0,0 +1,181 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use crate::explorer::{Key, View};
+
+use super::solution::{get_fs, Dir};
+
+// Browse the filesystem, expanding and collapsing directories
+pub struct TreeView {
+    root: Rc<RefCell<Dir>>,
+    // Paths of the expanded directories
+    expanded: HashSet<String>,
+    cursor: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct Row {
+    depth: usize,
+    path: String,
+    name: String,
+    size: u32,
+    is_dir: bool,
+}
+
+impl TreeView {
+    pub fn new(input: &str) -> Self {
+        let mut expanded = HashSet::new();
+        expanded.insert("/".to_string());
+        Self { root: get_fs(input), expanded, cursor: 0 }
+    }
+
+    // The rows that are visible with the current directories expanded
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        self.gather_rows(&self.root.borrow(), "/".to_string(), 0, &mut rows);
+        rows
+    }
+
+    fn gather_rows(&self, dir: &Dir, path: String, depth: usize, rows: &mut Vec<Row>) {
+        let expanded = self.expanded.contains(&path);
+        rows.push(Row { depth, path: path.clone(), name: dir.name.clone(), size: dir.get_size(), is_dir: true });
+        if !expanded {
+            return;
+        }
+        for d in &dir.dirs {
+            let d = d.borrow();
+            self.gather_rows(&d, format!("{}{}/", path, d.name), depth + 1, rows);
+        }
+        for f in &dir.files {
+            let f = f.borrow();
+            rows.push(Row { depth: depth + 1, path: format!("{}{}", path, f.name), name: f.name.clone(), size: f.size, is_dir: false });
+        }
+    }
+
+    fn move_cursor(&mut self, by: isize) {
+        let last = self.rows().len() - 1;
+        self.cursor = self.cursor.saturating_add_signed(by).min(last);
+    }
+
+    fn expand(&mut self, expand: bool) {
+        let rows = self.rows();
+        let row = &rows[self.cursor];
+        if row.is_dir && expand {
+            self.expanded.insert(row.path.clone());
+        } else if row.is_dir && self.expanded.contains(&row.path) {
+            self.expanded.remove(&row.path);
+        } else if let Some(parent) = rows[..self.cursor].iter().rposition(|r| r.depth < row.depth) {
+            self.cursor = parent;
+        }
+    }
+}
+
+impl View for TreeView {
+    fn title(&self) -> String {
+        format!("Day 7 - filesystem - {} used", self.root.borrow().get_size())
+    }
+
+    fn help(&self) -> &'static str {
+        "up/down: move  right: expand  left: collapse  enter: toggle"
+    }
+
+    fn render(&self, _width: usize, height: usize) -> Vec<String> {
+        let offset = (self.cursor + 1).saturating_sub(height);
+        self.rows()
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .map(|(idx, r)| {
+                let cursor = if idx == self.cursor { '>' } else { ' ' };
+                let icon = match (r.is_dir, self.expanded.contains(&r.path)) {
+                    (true, true) => '▾',
+                    (true, false) => '▸',
+                    (false, _) => ' ',
+                };
+                format!("{} {}{} {} ({})", cursor, "  ".repeat(r.depth), icon, r.name, r.size)
+            })
+            .collect()
+    }
+
+    fn handle_key(&mut self, key: Key) {
+        match key {
+            Key::Up => self.move_cursor(-1),
+            Key::Down => self.move_cursor(1),
+            Key::PageUp => self.move_cursor(-10),
+            Key::PageDown => self.move_cursor(10),
+            Key::Right => self.expand(true),
+            Key::Left => self.expand(false),
+            Key::Enter => {
+                let rows = self.rows();
+                let expand = !self.expanded.contains(&rows[self.cursor].path);
+                self.expand(expand);
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_INPUT: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn test_expand_and_collapse() {
+        let mut view = TreeView::new(TEST_INPUT);
+        assert_eq!(
+            view.render(80, 10),
+            vec![
+                "> ▾ / (48381165)",
+                "    ▸ a (94853)",
+                "    ▸ d (24933642)",
+                "      b.txt (14848514)",
+                "      c.dat (8504156)",
+            ]
+        );
+
+        view.handle_key(Key::Down);
+        view.handle_key(Key::Enter);
+        view.handle_key(Key::Down);
+        view.handle_key(Key::Right);
+        let lines = view.render(80, 10);
+        assert_eq!(lines[2], ">     ▾ e (584)");
+        assert_eq!(lines[3], "          i (584)");
+
+        // Left on a file moves to its directory, then collapses it
+        view.handle_key(Key::Down);
+        view.handle_key(Key::Left);
+        view.handle_key(Key::Left);
+        assert_eq!(view.render(80, 10)[2], ">     ▸ e (584)");
+    }
+
+    #[test]
+    fn test_render_scrolls_to_cursor() {
+        let mut view = TreeView::new(TEST_INPUT);
+        view.handle_key(Key::PageDown);
+        assert_eq!(view.render(80, 2), vec!["      b.txt (14848514)", ">     c.dat (8504156)"]);
+    }
+}
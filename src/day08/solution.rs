@@ -1,28 +1,37 @@
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
 
 use std::collections::HashSet;
 
 pub fn solution() {
-    let nums = parse_input(INPUT);
-    let grid = TreeGrid::new_from_vec(nums, 99);
-    let part1 = grid.count_high_trees();
-    println!("Solution for day eight part one = {}", part1); 
+    println!("Solution for day eight part one = {}", part1(INPUT)); 
+    println!("Solution for day eight part two = {}", part2(INPUT));
+}
+
+pub fn part1(input: &str) -> u32 {
+    get_grid(input).count_high_trees()
+}
+
+pub fn part2(input: &str) -> u32 {
+    get_grid(input).find_most_scenic_tree()
+}
 
-    println!("Solution for day eight part two = {}", grid.find_most_scenic_tree());
+pub fn get_grid(input: &str) -> TreeGrid {
+    let row_len = input.trim().split('\n').next().unwrap().len();
+    TreeGrid::new_from_vec(parse_input(input), row_len)
 }
 
 fn parse_input(input: &str) -> Vec<u32> {
   input.trim().replace("\n", "").chars().map(|c| c.to_digit(10).unwrap()).collect()
 }
 
-struct TreeGrid {
+pub struct TreeGrid {
     trees: Vec<(u32, usize)>,
     row_len: usize,
 }
 
 impl TreeGrid {
     pub fn new_from_vec(trees: Vec<u32>, row_len: usize) -> Self {
-        assert_eq!(trees.len() % row_len as usize, 0);
+        assert_eq!(trees.len() % row_len, 0);
         let trees_with_idx = trees.iter().enumerate().map(|(idx, n)| (*n, idx)).collect();
         Self {
             trees: trees_with_idx,
@@ -31,18 +40,18 @@ impl TreeGrid {
     }
 
     pub fn get_row(&self, row: usize) -> Vec<(u32, usize)> {
-        let start_idx = (self.row_len as usize) * row;
+        let start_idx = self.row_len * row;
         self.trees
             .iter()
             .skip(start_idx)
-            .take(self.row_len as usize)
+            .take(self.row_len)
             .cloned()
             .collect()
     }
 
     pub fn get_column(&self, col: usize) -> Vec<(u32, usize)> {
         (0..self.row_len)
-            .map(|n| (n * self.row_len) as usize + col)
+            .map(|n| (n * self.row_len) + col)
             .map(|idx| self.trees[idx])
             .collect()
     }
@@ -63,7 +72,7 @@ impl TreeGrid {
     }
 
     pub fn scenic_score_for_tree(&self, idx: usize) -> u32 {
-      let tree_size: u32 = self.trees[idx].0.clone();
+      let tree_size: u32 = self.trees[idx].0;
       let row = idx / self.row_len;
       let column = idx % self.row_len;
       let left: Vec<(u32, usize)> = self.get_row(row).iter().cloned().take(column).rev().collect();
@@ -77,7 +86,7 @@ impl TreeGrid {
       a * b * c * d
     }
 
-    pub fn find_highest_part2(nums: &Vec<(u32, usize)>, size: u32) -> u32 {
+    pub fn find_highest_part2(nums: &[(u32, usize)], size: u32) -> u32 {
       if nums.is_empty() {
         return 0;
       }
@@ -85,23 +94,23 @@ impl TreeGrid {
       let smaller_trees: Vec<(u32, usize)> = nums.iter()
           .take_while(|(n, _)| *n < size)
           .cloned().collect();
-      if (smaller_trees.len() == nums.len()) {
+      if smaller_trees.len() == nums.len() {
         smaller_trees.len() as u32
       } else {
         (smaller_trees.len() + 1) as u32
       }
     }
 
-    pub fn find_highest(nums: &Vec<(u32, usize)>, row_len: usize) -> Vec<(u32, usize)> {
-      let mut last_size: u32 = nums[0].0.clone();
+    pub fn find_highest(nums: &[(u32, usize)], row_len: usize) -> Vec<(u32, usize)> {
+      let mut last_size: u32 = nums[0].0;
       // skip first and last element
-      nums.iter().skip(1).take(row_len as usize - 2)
+      nums.iter().skip(1).take(row_len - 2)
           .filter(|(n, _)| Self::take_while_cnd(&mut last_size, n))
           .cloned()
           .collect()
     }
 
-    pub fn find_highest_both_dirs(nums: &Vec<(u32, usize)>, row_len: usize) -> Vec<(u32, usize)> {
+    pub fn find_highest_both_dirs(nums: &[(u32, usize)], row_len: usize) -> Vec<(u32, usize)> {
       let mut x = Self::find_highest(nums, row_len);
       let rev: Vec<(u32, usize)> = nums.iter().cloned().rev().collect();
       let mut y = Self::find_highest(&rev, row_len);
@@ -111,14 +120,14 @@ impl TreeGrid {
 
     pub fn count_high_trees(&self) -> u32 {
         let mut count: HashSet<usize> = HashSet::new();
-        for row_idx in 1..(self.row_len - 1) as usize {
+        for row_idx in 1..(self.row_len - 1) {
           let row: Vec<(u32,usize)> = self.get_row(row_idx);
-          Self::find_highest_both_dirs(&row, self.row_len as usize).iter().for_each(|(_, id)| {count.insert(*id);})
+          Self::find_highest_both_dirs(&row, self.row_len).iter().for_each(|(_, id)| {count.insert(*id);})
         }
 
-        for col_idx in 1..(self.row_len-1) as usize {
+        for col_idx in 1..(self.row_len-1) {
           let col= self.get_column(col_idx);
-          Self::find_highest_both_dirs(&col, self.row_len as usize).iter().for_each(|(_, id)| {count.insert(*id);})
+          Self::find_highest_both_dirs(&col, self.row_len).iter().for_each(|(_, id)| {count.insert(*id);})
         }
         (count.len() + (self.row_len*4) - 4) as u32
     }
@@ -189,7 +198,15 @@ mod test {
         ];
         let tree_grid = TreeGrid::new_from_vec(nums, 5);
         let scores: Vec<u32> = (0..25).map(|idx| tree_grid.scenic_score_for_tree(idx)).collect();
-        println!("scores = {:?}", scores);
-        assert!(false);
+        // The puzzle gives the 4 and the 8, and trees on the edge see nothing one way
+        assert_eq!(scores, vec![
+            0, 0, 0, 0, 0, 0, 1, 4, 1, 0, 0, 6, 1, 2, 0, 0, 1, 8, 3, 0, 0, 0, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(part1(INPUT), 1776);
+        assert_eq!(part2(INPUT), 234416);
     }
 }
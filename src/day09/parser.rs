@@ -1,15 +1,17 @@
-use nom::{IResult, character::complete::{one_of, digit1}, combinator::map_res, sequence::tuple, bytes::complete::tag, multi::separated_list1};
+use nom::{character::complete::{one_of, digit1}, combinator::map_res, sequence::tuple, error::context};
 use nom::character::complete::char;
 
+use crate::diagnostic::{expect, parse_all, separated_list1_strict, tag, IResult};
+
 use super::solution::Direction;
 
 pub fn parse_input(input: &str) -> Vec<Direction> {
-  let (_, dirs) = separated_list1(char('\n'), parse_line)(input).unwrap();
+  let dirs = parse_all(input, separated_list1_strict(char('\n'), parse_line)).unwrap_or_else(|e| panic!("{}", e));
   dirs.concat()
 }
 
 fn parse_direction(input: &str) -> IResult<&str, Direction> {
-  one_of("UDLR")(input).map(|(i, c)| (i, Direction::from(c)))
+  expect("a direction, U, D, L or R", one_of("UDLR"))(input).map(|(i, c)| (i, Direction::from(c)))
 }
 
 fn decimal(input: &str) -> IResult<&str, u32> {
@@ -17,7 +19,7 @@ fn decimal(input: &str) -> IResult<&str, u32> {
 }
 
 fn parse_line(input: &str) -> IResult<&str, Vec<Direction>> {
-  tuple((parse_direction, tag(" "), decimal))(input)
+  context("parse_line", tuple((parse_direction, tag(" "), decimal)))(input)
   .map(|(i, (d, _, n))| {
     let directions: Vec<Direction> = (0..n).map(|_| d).collect();
     (i, directions)
@@ -41,7 +43,7 @@ R 2";
     fn test_parse() {
       use super::super::solution::Direction::*;
 
-      let dirs = parse_input(&TEST_INPUT);
+      let dirs = parse_input(TEST_INPUT);
       let expected = vec![R,R,R,R,U,U,U,U,L,L,L,D,R,R,R,R,D,L,L,L,L,L,R,R];
       assert_eq!(dirs, expected);
     }
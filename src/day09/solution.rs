@@ -2,7 +2,8 @@ use std::{collections::HashSet, hash::Hash};
 
 use super::parser;
 
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(test)]
 const TEST_INPUT: &str = "R 4
 U 4
 L 3
@@ -12,6 +13,7 @@ D 1
 L 5
 R 2";
 
+#[cfg(test)]
 const TEST_INPUT_2: &str = "R 5
 U 8
 L 8
@@ -26,7 +28,7 @@ pub fn solution() {
     println!("Solution for day nine part two = {}", part2(INPUT)); 
 }
 
-fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> usize {
     let directions = parser::parse_input(input);
     let mut head: Point = Point::new(0, 0);
     let mut visited_by_head: Vec<Point> = Vec::new();
@@ -48,7 +50,7 @@ fn part1(input: &str) -> usize {
     visited_by_tail.len()
 }
 
-fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> usize {
     let directions = parser::parse_input(input);
     let visited_by_head = directions
         .iter()
@@ -149,7 +151,7 @@ impl Point {
             (_, -2) => Point::new(other.x, other.y + 1),
             (2, _) => Point::new(other.x - 1, other.y),
             (-2, _) => Point::new(other.x + 1, other.y),
-            _ => self.clone(),
+            _ => *self,
         }
     }
 }
@@ -1,2 +1,2 @@
 pub mod solution;
-mod parser;
\ No newline at end of file
+pub mod parser;
\ No newline at end of file
@@ -1,13 +1,14 @@
 use nom::branch::alt;
-use nom::{IResult, character::complete::digit1, combinator::map_res, sequence::tuple, bytes::complete::tag, multi::separated_list1};
+use nom::{character::complete::digit1, combinator::map_res, sequence::tuple, error::context};
 use nom::character::complete::char;
 use nom::combinator::opt;
 
+use crate::diagnostic::{parse_all, separated_list1_strict, tag, IResult};
+
 use super::solution::Instruction;
 
 pub fn parse_input(input: &str) -> Vec<Instruction> {
-  let (_, instructions) = separated_list1(char('\n'), parse_line)(input).unwrap();
-  instructions
+  parse_all(input, separated_list1_strict(char('\n'), parse_line)).unwrap_or_else(|e| panic!("{}", e))
 }
 
 fn parse_noop(input: &str) -> IResult<&str, Instruction> {
@@ -38,10 +39,10 @@ fn signed_decimal(input: &str) -> IResult<&str, i32> {
 }
 
 fn parse_line(input: &str) -> IResult<&str, Instruction> {
-  alt((
+  context("parse_line", alt((
     parse_noop,
     parse_addx
-  ))(input)
+  )))(input)
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 use super::parser;
 
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(test)]
 const TEST_INPUT: &str = include_str!("test_input.txt");
 
 pub fn solution() {
@@ -9,7 +10,7 @@ pub fn solution() {
     part2(INPUT);
 }
 
-fn part1(input: &str) -> i32 {
+pub fn part1(input: &str) -> i32 {
     let instructions = parser::parse_input(input);
     let modified_instructions = modify_instructions(&instructions);
 
@@ -28,6 +29,24 @@ fn part1(input: &str) -> i32 {
 }
 
 fn part2(input: &str) {
+    for (idx, c) in pixels(input).iter().enumerate() {
+        if ((idx) % 40) == 0 {
+            println!();
+        }
+        print!("{}", c);
+    }
+}
+
+// The letters part two draws, a line for each row of the screen
+pub fn screen(input: &str) -> String {
+    pixels(input)
+        .chunks(40)
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn pixels(input: &str) -> Vec<char> {
     let instructions = parser::parse_input(input);
     let modified_instructions = modify_instructions(&instructions);
 
@@ -42,13 +61,7 @@ fn part2(input: &str) {
         }
         sum += instruction.value();
     }
-
-    for (idx, c) in pixels.iter().enumerate() {
-        if ((idx) % 40) == 0 {
-            println!();
-        }
-        print!("{}", c);
-    }
+    pixels
 }
 
 // Insert a noop before each Addx. (Now each Addx will take two clock cycles)
@@ -82,7 +95,7 @@ impl Instruction {
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, TEST_INPUT};
+    use super::{part1, screen, INPUT, TEST_INPUT};
 
 
 
@@ -91,6 +104,12 @@ mod tests {
         assert_eq!(part1(TEST_INPUT), 13140);
     }
 
+    #[test]
+    fn test_answers() {
+        assert_eq!(part1(INPUT), 12560);
+        assert_eq!(screen(INPUT), crate::registry::DAY10_SCREEN);
+    }
+
 }
 
 // struct Cpu {
@@ -1,40 +1,41 @@
 use core::fmt;
 use std::cell::RefCell;
+use std::cmp::Reverse;
 
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::digit1,
     combinator::map_res,
-    multi::separated_list1,
+    error::context,
     sequence::{terminated, tuple},
-    IResult,
 };
 
-const INPUT: &str = include_str!("input.txt");
+use crate::diagnostic::{parse_all, separated_list1_strict, tag, IResult};
+
+pub const INPUT: &str = include_str!("input.txt");
 
 pub fn solution() {
-    println!("Solution for day eleven part one = {}", part1());
-    println!("Solution for day eleven part two = {}", part2());
+    println!("Solution for day eleven part one = {}", part1(INPUT));
+    println!("Solution for day eleven part two = {}", part2(INPUT));
 }
 
-fn part1() -> u64 {
-    let mut monkeys = Monkeys::new(parse_monkeys(INPUT), 3);
+pub fn part1(input: &str) -> u64 {
+    let mut monkeys = Monkeys::new(parse_monkeys(input), 3);
     monkeys.simulate_rounds(20);
     monkeys.calculate_monkey_business()
 }
 
-fn part2() -> u64 {
-    let mut monkeys = Monkeys::new(parse_monkeys(INPUT), 1);
+pub fn part2(input: &str) -> u64 {
+    let mut monkeys = Monkeys::new(parse_monkeys(input), 1);
     monkeys.simulate_rounds(10000);
     monkeys.calculate_monkey_business()
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct Test {
-    test: u64,
-    test_pass: usize,
-    test_fail: usize,
+    pub test: u64,
+    pub test_pass: usize,
+    pub test_fail: usize,
 }
 
 impl Test {
@@ -48,7 +49,7 @@ impl Test {
 }
 
 #[derive(Debug, PartialEq)]
-struct MonkeyMessage {
+pub struct MonkeyMessage {
     pub monkey_number: usize,
     pub item: u64,
 }
@@ -81,11 +82,11 @@ impl Operation {
 }
 
 #[derive(Clone, Debug)]
-struct Monkey {
-    items: Vec<u64>,
-    test: Test,
-    operation: Operation,
-    inspections: u64,
+pub struct Monkey {
+    pub items: Vec<u64>,
+    pub test: Test,
+    pub operation: Operation,
+    pub inspections: u64,
 }
 
 impl fmt::Display for Monkey {
@@ -100,7 +101,7 @@ impl fmt::Display for Monkey {
 
 impl Monkey {
     pub fn throw_to(&self, worry: u64) -> usize {
-        if (worry % self.test.test) == 0 {
+        if worry.is_multiple_of(self.test.test) {
             self.test.test_pass
         } else {
             self.test.test_fail
@@ -135,7 +136,7 @@ impl Monkey {
     }
 }
 
-struct Monkeys {
+pub struct Monkeys {
     pub monkeys: Vec<RefCell<Monkey>>,
     pub worry_divider: u64,
     // lowest common denominator
@@ -154,7 +155,7 @@ impl Monkeys {
 
     pub fn calculate_monkey_business(&mut self) -> u64 {
         self.monkeys
-            .sort_by(|a, b| b.borrow().inspections.cmp(&a.borrow().inspections));
+            .sort_by_key(|m| Reverse(m.borrow().inspections));
         self.monkeys[0].borrow().inspections * self.monkeys[1].borrow().inspections
     }
 
@@ -162,7 +163,7 @@ impl Monkeys {
         (0..rounds).for_each(|_| self.simulate_round());
     }
 
-    fn simulate_round(&mut self) {
+    pub fn simulate_round(&mut self) {
         for monkey in self.monkeys.iter() {
             let mut mut_mkey = monkey.borrow_mut();
             let messages = mut_mkey.calculate_worry_for_each_item(self.worry_divider, self.lcd);
@@ -187,7 +188,7 @@ impl fmt::Display for Monkeys {
 }
 
 pub fn parse_monkey_line(input: &str) -> IResult<&str, ()> {
-    tuple((tag("Monkey "), digit1, tag(":")))(input).map(|(i, _)| (i, ()))
+    context("parse_monkey_line", tuple((tag("Monkey "), digit1, tag(":"))))(input).map(|(i, _)| (i, ()))
 }
 
 fn decimal(input: &str) -> IResult<&str, u64> {
@@ -195,18 +196,18 @@ fn decimal(input: &str) -> IResult<&str, u64> {
 }
 
 fn parse_items_line(input: &str) -> IResult<&str, Vec<u64>> {
-    tuple((
-        tag("  Starting items: "),
-        separated_list1(tag(", "), decimal),
-    ))(input)
+    context(
+        "parse_items_line",
+        tuple((tag("  Starting items: "), separated_list1_strict(tag(", "), decimal))),
+    )(input)
     .map(|(i, (_, items))| (i, items))
 }
 
 fn parse_operation(input: &str) -> IResult<&str, Operation> {
-    tuple((
-        tag("  Operation: new = "),
-        alt((parse_square, parse_add, parse_multiply)),
-    ))(input)
+    context(
+        "parse_operation",
+        tuple((tag("  Operation: new = "), alt((parse_square, parse_add, parse_multiply)))),
+    )(input)
     .map(|(i, (_, o))| (i, o))
 }
 
@@ -235,21 +236,27 @@ fn parse_test_false(input: &str) -> IResult<&str, u64> {
 }
 
 fn parse_test_line(input: &str) -> IResult<&str, Test> {
-    tuple((
-        terminated(parse_test, tag("\n")),
-        terminated(parse_test_true, tag("\n")),
-        terminated(parse_test_false, tag("\n")),
-    ))(input)
+    context(
+        "parse_test_line",
+        tuple((
+            terminated(parse_test, tag("\n")),
+            terminated(parse_test_true, tag("\n")),
+            terminated(parse_test_false, tag("\n")),
+        )),
+    )(input)
     .map(|(i, (test, t, f))| (i, Test::new(test, t as usize, f as usize)))
 }
 
 fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
-    tuple((
-        terminated(parse_monkey_line, tag("\n")),
-        terminated(parse_items_line, tag("\n")),
-        terminated(parse_operation, tag("\n")),
-        parse_test_line,
-    ))(input)
+    context(
+        "parse_monkey",
+        tuple((
+            terminated(parse_monkey_line, tag("\n")),
+            terminated(parse_items_line, tag("\n")),
+            terminated(parse_operation, tag("\n")),
+            parse_test_line,
+        )),
+    )(input)
     .map(|(i, (_, items, operation, test))| {
         let m = Monkey {
             items,
@@ -261,9 +268,8 @@ fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
     })
 }
 
-fn parse_monkeys(input: &str) -> Vec<Monkey> {
-    let (_, monkeys) = separated_list1(tag("\n"), parse_monkey)(input).unwrap();
-    monkeys
+pub fn parse_monkeys(input: &str) -> Vec<Monkey> {
+    parse_all(input, separated_list1_strict(tag("\n"), parse_monkey)).unwrap_or_else(|e| panic!("{}", e))
 }
 
 #[cfg(test)]
@@ -315,11 +321,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part1(), 64032);
+        assert_eq!(part1(INPUT), 64032);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part2(), 12729522272);
+        assert_eq!(part2(INPUT), 12729522272);
     }
 }
@@ -0,0 +1,159 @@
+use crate::explorer::{Key, View};
+
+use super::solution::{parse_monkeys, Monkey, Monkeys, Operation};
+
+// Inspect each monkey, and play the rounds one at a time
+pub struct MonkeysView {
+    input: String,
+    monkeys: Monkeys,
+    selected: usize,
+    round: u32,
+}
+
+impl MonkeysView {
+    pub fn new(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+            monkeys: Monkeys::new(parse_monkeys(input), 3),
+            selected: 0,
+            round: 0,
+        }
+    }
+
+    fn reset(&mut self, worry_divider: u64) {
+        self.monkeys = Monkeys::new(parse_monkeys(&self.input), worry_divider);
+        self.round = 0;
+    }
+
+    fn simulate_rounds(&mut self, rounds: u32) {
+        self.monkeys.simulate_rounds(rounds);
+        self.round += rounds;
+    }
+
+    // Like calculate_monkey_business, without sorting the monkeys out of order
+    fn monkey_business(&self) -> u64 {
+        let mut inspections: Vec<u64> = self.monkeys.monkeys.iter().map(|m| m.borrow().inspections).collect();
+        inspections.sort_by(|a, b| b.cmp(a));
+        inspections.iter().take(2).product()
+    }
+
+    fn describe(monkey: &Monkey) -> Vec<String> {
+        let operation = match monkey.operation {
+            Operation::Add(n) => format!("old + {}", n),
+            Operation::Multiply(n) => format!("old * {}", n),
+            Operation::Square => "old * old".to_string(),
+        };
+        let items: Vec<String> = monkey.items.iter().map(|i| i.to_string()).collect();
+        vec![
+            format!("  Operation: new = {}", operation),
+            format!("  Test: divisible by {}", monkey.test.test),
+            format!("    If true: throw to monkey {}", monkey.test.test_pass),
+            format!("    If false: throw to monkey {}", monkey.test.test_fail),
+            format!("  Inspections: {}", monkey.inspections),
+            format!("  Items ({}): {}", items.len(), items.join(", ")),
+        ]
+    }
+}
+
+impl View for MonkeysView {
+    fn title(&self) -> String {
+        format!(
+            "Day 11 - worry divider {} - round {} - monkey business {}",
+            self.monkeys.worry_divider,
+            self.round,
+            self.monkey_business()
+        )
+    }
+
+    fn help(&self) -> &'static str {
+        "up/down: select monkey  right: next round  pgdn: 100 rounds  1/2: restart as part one/two"
+    }
+
+    fn render(&self, _width: usize, height: usize) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .monkeys
+            .monkeys
+            .iter()
+            .enumerate()
+            .map(|(idx, m)| {
+                let m = m.borrow();
+                let cursor = if idx == self.selected { '>' } else { ' ' };
+                format!("{} Monkey {}: {} inspections, {} items", cursor, idx, m.inspections, m.items.len())
+            })
+            .collect();
+        lines.push(String::new());
+        lines.push(format!("Monkey {}:", self.selected));
+        lines.extend(Self::describe(&self.monkeys.monkeys[self.selected].borrow()));
+        lines.truncate(height);
+        lines
+    }
+
+    fn handle_key(&mut self, key: Key) {
+        match key {
+            Key::Up => self.selected = self.selected.saturating_sub(1),
+            Key::Down => self.selected = (self.selected + 1).min(self.monkeys.monkeys.len() - 1),
+            Key::Right | Key::Char('n') => self.simulate_rounds(1),
+            Key::PageDown => self.simulate_rounds(100),
+            Key::Char('1') => self.reset(3),
+            Key::Char('2') => self.reset(1),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_INPUT: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn test_inspect_monkey() {
+        let mut view = MonkeysView::new(TEST_INPUT);
+        view.handle_key(Key::Down);
+        view.handle_key(Key::Down);
+        let lines = view.render(80, 20);
+        assert_eq!(lines[2], "> Monkey 2: 0 inspections, 3 items");
+        assert_eq!(lines[5], "Monkey 2:");
+        assert_eq!(lines[6], "  Operation: new = old * old");
+        assert_eq!(lines[11], "  Items (3): 79, 60, 97");
+    }
+
+    #[test]
+    fn test_play_rounds() {
+        let mut view = MonkeysView::new(TEST_INPUT);
+        (0..20).for_each(|_| view.handle_key(Key::Right));
+        assert_eq!(view.title(), "Day 11 - worry divider 3 - round 20 - monkey business 10605");
+
+        view.handle_key(Key::Char('2'));
+        assert_eq!(view.round, 0);
+        assert_eq!(view.monkeys.worry_divider, 1);
+    }
+}
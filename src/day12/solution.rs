@@ -1,8 +1,9 @@
 use priority_queue::PriorityQueue;
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
 
+#[cfg(test)]
 const TEST_INPUT: &str = "Sabqponm
 abcryxxl
 accszExk
@@ -14,14 +15,14 @@ pub fn solution() {
     println!("Solution for day twelve part two = {}", part2(INPUT));
 }
 
-fn get_height_map(input: &str) -> HeightMap {
+pub fn get_height_map(input: &str) -> HeightMap {
     let heights = parse_input(input);
     let cols = input.chars().take_while(|c| *c != '\n').count();
     let rows = input.split('\n').count();
     HeightMap::new(heights, rows, cols)
 }
 
-fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> usize {
     let chars: Vec<char> = input.replace('\n', "").chars().collect();
     let low_points: Vec<usize> = chars
         .iter()
@@ -41,7 +42,7 @@ fn part2(input: &str) -> usize {
     distances[0]
 }
 
-fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> usize {
     let chars: Vec<char> = input.replace('\n', "").chars().collect();
     let start_idx = chars.iter().position(|c| *c == 'S').unwrap();
     let end_idx = chars.iter().position(|c| *c == 'E').unwrap();
@@ -83,6 +84,8 @@ fn a_star_algo(hmap: &HeightMap, start_idx: usize, end_idx: usize) -> usize {
     path.len()
 }
 
+// For debugging, see the commented out call in part1
+#[allow(dead_code)]
 fn pretty_print_path(hmap: &HeightMap, path: &[usize]) {
     for (i, _) in hmap.heights.iter().enumerate() {
         if (i % hmap.cols) == 0 {
@@ -112,7 +115,7 @@ fn get_path(paths: HashMap<usize, Option<usize>>, start: usize, goal: usize) ->
 }
 
 #[derive(Debug, PartialEq)]
-struct HeightMap {
+pub struct HeightMap {
     heights: Vec<i32>,
     rows: usize,
     cols: usize,
@@ -144,7 +147,7 @@ impl HeightMap {
         let row_range = (row * self.cols)..(row * self.cols + self.cols);
 
         // Get the indexes of the four neighbours of idx
-        let neighbour_indexes: Vec<usize> = vec![
+        let neighbour_indexes: Vec<usize> = [
             idx.checked_sub(self.cols), // index for element above
             idx.checked_add(self.cols), // index for element below
             idx.checked_sub(1).filter(|i| row_range.contains(i)), // index for element to the left
@@ -168,7 +171,7 @@ impl HeightMap {
 }
 
 #[derive(Debug, PartialEq)]
-struct Node {
+pub struct Node {
     pub id: usize,
     pub height: i32,
     pub neighbours: Vec<usize>,
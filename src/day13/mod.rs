@@ -1,2 +1,4 @@
 pub mod solution;
+// Unfinished, only its test runs it so far
+#[cfg(test)]
 mod parser;
\ No newline at end of file
@@ -1,8 +1,6 @@
 use std::{rc::Rc, cell::RefCell};
 
-use nom::{combinator::map_res, IResult, character::complete::digit1, multi::{separated_list1, separated_list0, many1}, bytes::complete::{tag, is_a}, branch::alt};
-
-use super::solution::{Data, Packet};
+use nom::{combinator::map_res, IResult, character::complete::digit1, multi::many1, bytes::complete::tag, branch::alt};
 
 #[derive(PartialEq, PartialOrd, Debug)]
 enum Token {
@@ -12,7 +10,9 @@ enum Token {
     Comma,
 }
 
+// The tree of packets isn't finished, nothing builds one yet
 #[derive(PartialEq, Debug)]
+#[allow(dead_code)]
 struct Node {
 //   pub nums: RefCell<Vec<i32>>,
   pub nums: Vec<i32>,
@@ -53,6 +53,7 @@ fn decimal(input: &str) -> IResult<&str, i32> {
   map_res(digit1, str::parse)(input)
 }
 
+#[allow(dead_code)]
 fn build_tree(lines: Vec<Token>) -> Rc<RefCell<Node>> {
   let root = Rc::new(RefCell::new(Node {nums: Vec::new(), nodes: Vec::new(), parent: None }));
   let mut current_node: Rc<RefCell<Node>> = Rc::clone(&root);
@@ -96,7 +97,7 @@ fn build_tree(lines: Vec<Token>) -> Rc<RefCell<Node>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::day13::{parser::parse_line, solution::Packet};
+    use crate::day13::parser::parse_line;
 
     #[test]
     fn test_parse_line() {
@@ -1,22 +1,23 @@
-use std::fmt;
-
 pub fn solution() {
     println!("Solution for day twelve part one = ???");
     println!("Solution for day twelve part two = ???");
 }
 
+// The packets and their comparison aren't finished, nothing calls them yet
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 pub enum Data {
     Int(i32),
     List(Vec<Data>),
 }
 
+#[allow(dead_code)]
 impl Data {
     pub fn make_list(&self) -> Data {
         use Data::*;
         match self {
             Int(x) => List(vec![Int(*x)]),
-            list => self.clone(),
+            _ => self.clone(),
         }
     }
 }
@@ -31,6 +32,7 @@ impl Data {
 //     }
 // }
 
+#[allow(dead_code)]
 pub fn compare(lhs: &mut Packet, rhs: &mut Packet) -> bool {
     use Data::*;
     let left_data = lhs.pop();
@@ -52,4 +54,5 @@ pub fn compare(lhs: &mut Packet, rhs: &mut Packet) -> bool {
     }
 }
 
+#[allow(dead_code)]
 pub type Packet = Vec<Data>;
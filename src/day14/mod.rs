@@ -1 +1,2 @@
 pub mod solution;
+pub mod view;
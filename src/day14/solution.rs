@@ -1,10 +1,12 @@
 use nom::{
-    bytes::complete::tag, character::complete::digit1, combinator::map_res, multi::separated_list1,
-    sequence::tuple, IResult,
+    character::complete::digit1, combinator::map_res, error::context, multi::separated_list1,
+    sequence::tuple,
 };
 use std::{collections::HashMap, hash::Hash};
 
-const INPUT: &str = include_str!("input.txt");
+use crate::diagnostic::{parse_all, separated_list1_strict, tag, IResult};
+
+pub const INPUT: &str = include_str!("input.txt");
 
 pub fn solution() {
     println!("Solution for day twelve part one = {}", part1(INPUT));
@@ -13,7 +15,7 @@ pub fn solution() {
 
 pub type Cave = HashMap<Point, Marker>;
 
-fn get_lowest_point(points: &[Vec<Point>]) -> usize {
+pub fn get_lowest_point(points: &[Vec<Point>]) -> usize {
     let mut all_points: Vec<usize> = points.iter().flatten().map(|p| p.y).collect();
     all_points.sort_by(|a, b| b.cmp(a));
     all_points[0]
@@ -36,7 +38,7 @@ fn interpolate_point(start: Point, end: Point) -> Vec<Point> {
     }
 }
 
-fn interpolate_points(points: &[Vec<Point>]) -> Vec<Point> {
+pub fn interpolate_points(points: &[Vec<Point>]) -> Vec<Point> {
     points
         .iter()
         .flat_map(|line| {
@@ -49,7 +51,7 @@ fn interpolate_points(points: &[Vec<Point>]) -> Vec<Point> {
         .collect()
 }
 
-fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> usize {
     let coords = parse_all_input(input);
 
     // Interpolate the coordinate into lines of rock
@@ -66,7 +68,7 @@ fn part1(input: &str) -> usize {
     map.values().filter(|v| **v == Marker::Sand).count()
 }
 
-fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> usize {
     let mut coords = parse_all_input(input);
     let floor = get_lowest_point(&coords) + 2;
 
@@ -91,7 +93,7 @@ fn part2(input: &str) -> usize {
     map.values().filter(|v| **v == Marker::Sand).count()
 }
 
-fn simulate_sand(point: Point, goal_y: usize, map: &mut Cave) -> bool {
+pub fn simulate_sand(point: Point, goal_y: usize, map: &mut Cave) -> bool {
     if point.y >= goal_y {
         return true;
     }
@@ -164,16 +166,15 @@ fn parse_coordinate(input: &str) -> IResult<&str, Point> {
 }
 
 fn parse_line(input: &str) -> IResult<&str, Vec<Point>> {
-    separated_list1(parse_arrow, parse_coordinate)(input)
+    context("parse_line", separated_list1(parse_arrow, parse_coordinate))(input)
 }
 
 fn parse_arrow(input: &str) -> IResult<&str, &str> {
     tag(" -> ")(input)
 }
 
-fn parse_all_input(input: &str) -> Vec<Vec<Point>> {
-    let (_, output) = separated_list1(tag("\n"), parse_line)(input).unwrap();
-    output
+pub fn parse_all_input(input: &str) -> Vec<Vec<Point>> {
+    parse_all(input, separated_list1_strict(tag("\n"), parse_line)).unwrap_or_else(|e| panic!("{}", e))
 }
 
 #[cfg(test)]
@@ -250,4 +251,10 @@ mod tests {
         let answer = map.values().filter(|v| **v == Marker::Sand).count();
         assert_eq!(answer, 24);
     }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(part1(INPUT), 843);
+        assert_eq!(part2(INPUT), 27625);
+    }
 }
@@ -0,0 +1,152 @@
+use crate::explorer::{Key, View};
+
+use super::solution::{get_lowest_point, interpolate_points, parse_all_input, simulate_sand, Cave, Marker, Point};
+
+const SAND_START: Point = Point { x: 500, y: 0 };
+
+// Pan around the cave and pour the sand in, one unit at a time
+pub struct CaveView {
+    input: String,
+    cave: Cave,
+    // Sand falling below this point has fallen into the abyss
+    goal: usize,
+    with_floor: bool,
+    finished: bool,
+    // The point at the centre top of the screen
+    centre_x: usize,
+    top_y: usize,
+}
+
+impl CaveView {
+    pub fn new(input: &str) -> Self {
+        let mut view = Self {
+            input: input.to_string(),
+            cave: Cave::new(),
+            goal: 0,
+            with_floor: false,
+            finished: false,
+            centre_x: SAND_START.x,
+            top_y: 0,
+        };
+        view.reset(false);
+        view
+    }
+
+    // Build the cave for part one, or with the floor for part two
+    fn reset(&mut self, with_floor: bool) {
+        let mut coords = parse_all_input(&self.input);
+        let lowest = get_lowest_point(&coords);
+        if with_floor {
+            coords.push(vec![Point::new(0, lowest + 2), Point::new(SAND_START.x * 2, lowest + 2)]);
+        }
+        self.cave = interpolate_points(&coords).into_iter().map(|p| (p, Marker::Rock)).collect();
+        self.goal = if with_floor { lowest + 2 } else { lowest };
+        self.with_floor = with_floor;
+        self.finished = false;
+    }
+
+    fn pour(&mut self) {
+        if !self.finished {
+            self.finished = simulate_sand(SAND_START, self.goal, &mut self.cave) || self.cave.contains_key(&SAND_START);
+        }
+    }
+
+    fn sand(&self) -> usize {
+        self.cave.values().filter(|m| **m == Marker::Sand).count()
+    }
+}
+
+impl View for CaveView {
+    fn title(&self) -> String {
+        let part = if self.with_floor { "with floor" } else { "without floor" };
+        let state = if self.finished { " - finished" } else { "" };
+        format!(
+            "Day 14 - {} - {} units of sand{} - viewing x {} y {}",
+            part,
+            self.sand(),
+            state,
+            self.centre_x,
+            self.top_y
+        )
+    }
+
+    fn help(&self) -> &'static str {
+        "arrows: pan  pgup/pgdn: pan faster  s: drop sand  f: fill  1/2: restart as part one/two"
+    }
+
+    fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let left_x = self.centre_x.saturating_sub(width / 2);
+        (self.top_y..self.top_y + height)
+            .map(|y| {
+                (left_x..left_x + width)
+                    .map(|x| match self.cave.get(&Point::new(x, y)) {
+                        Some(Marker::Rock) => '#',
+                        Some(Marker::Sand) => 'o',
+                        None if x == SAND_START.x && y == SAND_START.y => '+',
+                        None => '.',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn handle_key(&mut self, key: Key) {
+        match key {
+            Key::Up => self.top_y = self.top_y.saturating_sub(1),
+            Key::Down => self.top_y += 1,
+            Key::Left => self.centre_x = self.centre_x.saturating_sub(1),
+            Key::Right => self.centre_x += 1,
+            Key::PageUp => self.top_y = self.top_y.saturating_sub(10),
+            Key::PageDown => self.top_y += 10,
+            Key::Char('s') | Key::Enter => self.pour(),
+            Key::Char('f') => {
+                while !self.finished {
+                    self.pour()
+                }
+            }
+            Key::Char('1') => self.reset(false),
+            Key::Char('2') => self.reset(true),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_INPUT: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn test_render() {
+        let mut view = CaveView::new(TEST_INPUT);
+        view.handle_key(Key::Left);
+        (0..5).for_each(|_| view.handle_key(Key::Char('s')));
+        assert_eq!(
+            view.render(10, 10),
+            vec![
+                "......+...",
+                "..........",
+                "..........",
+                "..........",
+                "....#...##",
+                "....#...#.",
+                "..###...#.",
+                "......o.#.",
+                "....oooo#.",
+                "#########.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut view = CaveView::new(TEST_INPUT);
+        view.handle_key(Key::Char('f'));
+        assert_eq!(view.sand(), 24);
+        view.handle_key(Key::Char('2'));
+        view.handle_key(Key::Char('f'));
+        assert_eq!(view.sand(), 93);
+    }
+}
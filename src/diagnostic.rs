@@ -0,0 +1,254 @@
+use std::fmt;
+
+use nom::{
+    error::{ContextError, ErrorKind, FromExternalError},
+    InputLength, Parser,
+};
+
+// The days' parsers use this in place of nom's IResult, so that a failure can
+// be rendered as a Diagnostic rather than a dump of the rest of the input
+pub type IResult<I, O> = nom::IResult<I, O, ParseError<I>>;
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError<I> {
+    // The input left when the parser failed
+    pub input: I,
+    // What the parser would have accepted at that point
+    pub expected: Vec<String>,
+    // The names of the parsers that were running, innermost first
+    pub contexts: Vec<&'static str>,
+}
+
+impl<I> ParseError<I> {
+    fn new(input: I, expected: String) -> Self {
+        Self { input, expected: vec![expected], contexts: Vec::new() }
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "a number".to_string(),
+        ErrorKind::Alpha => "a letter".to_string(),
+        ErrorKind::MapRes => "a number that fits".to_string(),
+        ErrorKind::Eof => "the end of the input".to_string(),
+        kind => format!("{:?}", kind).to_lowercase(),
+    }
+}
+
+impl<I: InputLength> nom::error::ParseError<I> for ParseError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self::new(input, describe(kind))
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Self::new(input, format!("{:?}", c))
+    }
+
+    // Used by alt, keep the error from the alternative that got the furthest.
+    // If they failed at the same point then any of them would have done.
+    fn or(mut self, other: Self) -> Self {
+        match self.input.input_len().cmp(&other.input.input_len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                for e in other.expected {
+                    if !self.expected.contains(&e) {
+                        self.expected.push(e);
+                    }
+                }
+                self.contexts.clear();
+                self
+            }
+        }
+    }
+}
+
+impl<I> ContextError<I> for ParseError<I> {
+    fn add_context(_: I, context: &'static str, mut other: Self) -> Self {
+        other.contexts.push(context);
+        other
+    }
+}
+
+impl<I, E> FromExternalError<I, E> for ParseError<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _: E) -> Self {
+        Self::new(input, describe(kind))
+    }
+}
+
+// Describe what the parser expects, when it fails without consuming anything
+pub fn expect<'a, O, F>(description: &'static str, mut parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, ParseError<&'a str>>,
+{
+    move |input| {
+        parser.parse(input).map_err(|e| {
+            e.map(|mut err| {
+                if err.input.len() == input.len() {
+                    err.expected = vec![description.to_string()];
+                }
+                err
+            })
+        })
+    }
+}
+
+// nom's tag, but a failure says which tag was expected
+pub fn tag<'a>(t: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    move |input| {
+        nom::bytes::complete::tag(t)(input)
+            .map_err(|e: nom::Err<ParseError<&str>>| e.map(|err| ParseError::new(err.input, format!("{:?}", t))))
+    }
+}
+
+// Like separated_list1, but an element that fails after a separator is an
+// error, rather than the end of the list. Unless only whitespace is left.
+pub fn separated_list1_strict<'a, O, O2, F, G>(mut sep: G, mut f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, ParseError<&'a str>>,
+    G: Parser<&'a str, O2, ParseError<&'a str>>,
+{
+    move |input| {
+        let (mut input, first) = f.parse(input)?;
+        let mut items = vec![first];
+        loop {
+            let after_sep = match sep.parse(input) {
+                Ok((i, _)) => i,
+                Err(nom::Err::Error(_)) => return Ok((input, items)),
+                Err(e) => return Err(e),
+            };
+            if after_sep.trim().is_empty() {
+                return Ok((input, items));
+            }
+            let (rest, item) = f.parse(after_sep)?;
+            items.push(item);
+            input = rest;
+        }
+    }
+}
+
+// Run the parser over the whole of the source, anything but whitespace left over is an error
+pub fn parse_all<'a, O, F>(source: &'a str, mut parser: F) -> Result<O, Diagnostic>
+where
+    F: Parser<&'a str, O, ParseError<&'a str>>,
+{
+    match parser.parse(source) {
+        Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+        Ok((rest, _)) => Err(Diagnostic::new(source, ParseError::new(rest, "the end of the input".to_string()))),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(Diagnostic::new(source, e)),
+        Err(nom::Err::Incomplete(_)) => Err(Diagnostic::new(source, ParseError::new("", "more input".to_string()))),
+    }
+}
+
+// A parse failure, pointing at the line and column it happened
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub source_line: String,
+    pub expected: Vec<String>,
+    // The names of the parsers that were running, outermost first
+    pub parsers: Vec<&'static str>,
+}
+
+impl Diagnostic {
+    pub fn new(source: &str, error: ParseError<&str>) -> Self {
+        let offset = source.len() - error.input.len();
+        let line_start = source[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = source[offset..].find('\n').map(|i| i + offset).unwrap_or(source.len());
+        Self {
+            line: source[..offset].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            source_line: source[line_start..line_end].to_string(),
+            expected: error.expected,
+            parsers: error.contexts.into_iter().rev().collect(),
+        }
+    }
+
+    fn expected_list(&self) -> String {
+        match self.expected.as_slice() {
+            [] => "something else".to_string(),
+            [one] => one.clone(),
+            [rest @ .., last] => format!("one of {} or {}", rest.join(", "), last),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        match self.parsers.last() {
+            Some(parser) => writeln!(f, "error: {} failed, expected {}", parser, self.expected_list())?,
+            None => writeln!(f, "error: expected {}", self.expected_list())?,
+        }
+        write!(f, "{}--> line {}, column {}", gutter, self.line, self.column)?;
+        if self.parsers.len() > 1 {
+            write!(f, " (in {})", self.parsers.join(" > "))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nom::{branch::alt, character::complete::digit1, error::context, sequence::tuple};
+
+    fn parse_move(input: &str) -> IResult<&str, (&str, &str)> {
+        context("parse_move", tuple((tag("move "), digit1, tag(" to "), digit1)))(input)
+            .map(|(i, (_, n, _, to))| (i, (n, to)))
+    }
+
+    fn parse_line(input: &str) -> IResult<&str, (&str, &str)> {
+        context("parse_line", alt((parse_move, expect("a comment", tuple((tag("#"), tag(" ")))))))(input)
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let source = "move 1 to 2\nmove 3 fro 4\nmove 5 to 6";
+        let err = parse_all(source, separated_list1_strict(tag("\n"), parse_move)).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 7);
+        assert_eq!(err.expected, vec!["\" to \""]);
+        assert_eq!(err.parsers, vec!["parse_move"]);
+        assert_eq!(
+            err.to_string(),
+            "error: parse_move failed, expected \" to \"
+ --> line 2, column 7
+  |
+2 | move 3 fro 4
+  |       ^"
+        );
+    }
+
+    #[test]
+    fn test_alternatives_are_merged() {
+        let err = parse_all("move 1 to 2\nmvoe 3 to 4\n", separated_list1_strict(tag("\n"), parse_line)).unwrap_err();
+        assert_eq!(err.column, 1);
+        assert_eq!(err.parsers, vec!["parse_line"]);
+        assert_eq!(err.expected_list(), "one of \"move \" or a comment");
+    }
+
+    #[test]
+    fn test_nested_contexts() {
+        let err = parse_all("move x to 2", parse_line).unwrap_err();
+        assert_eq!(err.parsers, vec!["parse_line", "parse_move"]);
+        assert_eq!(err.expected, vec!["a number"]);
+        assert!(err.to_string().contains("--> line 1, column 6 (in parse_line > parse_move)"));
+    }
+
+    #[test]
+    fn test_leftover_input() {
+        let err = parse_all("move 1 to 2\nmove 3 to 4 and 5", separated_list1_strict(tag("\n"), parse_move)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 12));
+        assert_eq!(err.expected, vec!["the end of the input"]);
+        assert!(parse_all("move 1 to 2\n\n", separated_list1_strict(tag("\n"), parse_move)).is_ok());
+    }
+}
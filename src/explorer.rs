@@ -0,0 +1,133 @@
+use std::io::{self, Write};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::{day05, day07, day11, day14};
+
+// The keys a view can react to. 'q' and Esc are kept by the explorer to quit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    PageUp,
+    PageDown,
+    Char(char),
+}
+
+// A browsable view of a day's parsed input and intermediate state
+pub trait View {
+    fn title(&self) -> String;
+    // A one line summary of the keys the view understands
+    fn help(&self) -> &'static str;
+    // Render the view to at most `height` lines, the explorer clips each line to `width`
+    fn render(&self, width: usize, height: usize) -> Vec<String>;
+    fn handle_key(&mut self, key: Key);
+}
+
+pub fn view_for_day(day: u8, input: &str) -> Option<Box<dyn View>> {
+    match day {
+        5 => Some(Box::new(day05::view::StacksView::new(input))),
+        7 => Some(Box::new(day07::view::TreeView::new(input))),
+        11 => Some(Box::new(day11::view::MonkeysView::new(input))),
+        14 => Some(Box::new(day14::view::CaveView::new(input))),
+        _ => None,
+    }
+}
+
+pub fn run(view: &mut dyn View) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let _terminal = RawTerminal::enter(&mut stdout)?;
+    event_loop(view, &mut stdout)
+}
+
+// Raw mode and the alternate screen for as long as it's alive. Dropping it puts the
+// terminal back, whether the explorer quits, returns an error or panics.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter(stdout: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        // Made before the screen is switched, so a failure there still leaves raw mode
+        let raw_terminal = Self;
+        execute!(stdout, EnterAlternateScreen, cursor::Hide)?;
+        Ok(raw_terminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        // Nothing more can be done if these fail, carry on so raw mode is still left
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn event_loop(view: &mut dyn View, stdout: &mut impl Write) -> io::Result<()> {
+    loop {
+        draw(view, stdout)?;
+        match event::read()? {
+            Event::Key(k) if k.kind == KeyEventKind::Press => match k.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                code => {
+                    if let Some(key) = to_key(code) {
+                        view.handle_key(key);
+                    }
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+fn to_key(code: KeyCode) -> Option<Key> {
+    match code {
+        KeyCode::Up => Some(Key::Up),
+        KeyCode::Down => Some(Key::Down),
+        KeyCode::Left => Some(Key::Left),
+        KeyCode::Right => Some(Key::Right),
+        KeyCode::Enter => Some(Key::Enter),
+        KeyCode::PageUp => Some(Key::PageUp),
+        KeyCode::PageDown => Some(Key::PageDown),
+        KeyCode::Char(c) => Some(Key::Char(c)),
+        _ => None,
+    }
+}
+
+fn draw(view: &dyn View, stdout: &mut impl Write) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, height) = (width as usize, height as usize);
+    let body_height = height.saturating_sub(2);
+
+    queue!(stdout, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+    queue!(
+        stdout,
+        SetAttribute(Attribute::Reverse),
+        Print(fit(&view.title(), width)),
+        SetAttribute(Attribute::Reset)
+    )?;
+    for (row, line) in view.render(width, body_height).iter().take(body_height).enumerate() {
+        queue!(stdout, cursor::MoveTo(0, row as u16 + 1), Print(fit(line, width)))?;
+    }
+    queue!(
+        stdout,
+        cursor::MoveTo(0, height.saturating_sub(1) as u16),
+        SetAttribute(Attribute::Dim),
+        Print(fit(&format!("{}  q: quit", view.help()), width)),
+        SetAttribute(Attribute::Reset)
+    )?;
+    stdout.flush()
+}
+
+// Pad or cut a line to exactly `width` characters
+fn fit(line: &str, width: usize) -> String {
+    format!("{:width$}", line.chars().take(width).collect::<String>(), width = width)
+}
@@ -0,0 +1,217 @@
+use std::{cmp, fmt};
+
+// A closed range of whole numbers, both ends included, so 2-4 is 2, 3 and 4
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+// Not every puzzle needs all of it, day04 and the coverage report use most
+#[allow(dead_code)]
+impl Interval {
+    // None if the start is after the end
+    pub fn new(start: i64, end: i64) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    pub fn point(value: i64) -> Self {
+        Self { start: value, end: value }
+    }
+
+    // The number of values in it, never zero. The whole of i64 has one more than u64 can
+    // hold, so it's a u128.
+    pub fn len(&self) -> u128 {
+        self.start.abs_diff(self.end) as u128 + 1
+    }
+
+    pub fn includes(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    // Whether the other is wholly inside this one
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    // They touch end to end without sharing a value, like 2-4 and 5-7
+    pub fn is_adjacent(&self, other: &Self) -> bool {
+        self.end.checked_add(1) == Some(other.start) || other.end.checked_add(1) == Some(self.start)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(cmp::max(self.start, other.start), cmp::min(self.end, other.end))
+    }
+
+    // None when there'd be a gap between them, an IntervalSet can hold both then
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        (self.overlaps(other) || self.is_adjacent(other))
+            .then(|| Self { start: cmp::min(self.start, other.start), end: cmp::max(self.end, other.end) })
+    }
+
+    // What's left of this one without the other, up to a piece either side of it
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        let before = other.start.checked_sub(1).and_then(|end| Self::new(self.start, end));
+        let after = other.end.checked_add(1).and_then(|start| Self::new(start, self.end));
+        before.into_iter().chain(after).collect()
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+// A set of values kept as sorted intervals, with no two of them overlapping or adjacent
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<Interval>,
+}
+
+#[allow(dead_code)]
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ranges(&self) -> &[Interval] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // The number of values in the set
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().map(Interval::len).sum()
+    }
+
+    pub fn includes(&self, value: i64) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end < value);
+        self.ranges.get(idx).is_some_and(|r| r.includes(value))
+    }
+
+    // Joins the interval with any it overlaps or touches
+    pub fn insert(&mut self, interval: Interval) {
+        // The ranges from first to last are the ones it joins up with
+        let first = self.ranges.partition_point(|r| r.end.saturating_add(1) < interval.start);
+        let last = self.ranges.partition_point(|r| r.start <= interval.end.saturating_add(1));
+        let merged = self.ranges[first..last].iter().fold(interval, |m, r| m.union(r).unwrap());
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: Interval) {
+        let first = self.ranges.partition_point(|r| r.end < interval.start);
+        let last = self.ranges.partition_point(|r| r.start <= interval.end);
+        let left: Vec<Interval> = self.ranges[first..last].iter().flat_map(|r| r.difference(&interval)).collect();
+        self.ranges.splice(first..last, left);
+    }
+
+    // Adds everything in the other set
+    pub fn merge(&mut self, other: &Self) {
+        other.ranges.iter().for_each(|r| self.insert(*r));
+    }
+
+    // The values between the first and last that aren't in the set
+    pub fn gaps(&self) -> Vec<Interval> {
+        self.ranges.windows(2).filter_map(|w| Interval::new(w[0].end + 1, w[1].start - 1)).collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|r| set.insert(r));
+        set
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranges: Vec<String> = self.ranges.iter().map(Interval::to_string).collect();
+        write!(f, "{}", ranges.join(", "))
+    }
+}
+
+// Short for Interval::new in the tests, which only build valid intervals
+#[cfg(test)]
+pub fn i(start: i64, end: i64) -> Interval {
+    Interval::new(start, end).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(Interval::new(4, 2), None);
+        assert_eq!(i(2, 4).len(), 3);
+        assert_eq!(Interval::point(-3).len(), 1);
+        assert_eq!(i(i64::MIN, i64::MAX).len(), 1 << 64);
+        assert!(i(2, 8).contains(&i(3, 7)));
+        assert!(!i(3, 7).contains(&i(2, 8)));
+        assert!(i(5, 7).overlaps(&i(7, 9)));
+        assert!(!i(2, 4).overlaps(&i(6, 8)));
+        assert!(i(2, 4).is_adjacent(&i(5, 7)));
+        assert!(!i(2, 4).is_adjacent(&i(4, 7)));
+        assert!(!i(2, 4).is_adjacent(&i(6, 7)));
+    }
+
+    #[test]
+    fn test_interval_algebra() {
+        assert_eq!(i(2, 6).intersection(&i(4, 8)), Some(i(4, 6)));
+        assert_eq!(i(2, 4).intersection(&i(6, 8)), None);
+        assert_eq!(i(2, 4).union(&i(5, 7)), Some(i(2, 7)));
+        assert_eq!(i(2, 4).union(&i(6, 8)), None);
+        assert_eq!(i(2, 8).difference(&i(4, 5)), vec![i(2, 3), i(6, 8)]);
+        assert_eq!(i(2, 8).difference(&i(1, 5)), vec![i(6, 8)]);
+        assert_eq!(i(2, 8).difference(&i(2, 8)), vec![]);
+        assert_eq!(i(2, 4).difference(&i(6, 8)), vec![i(2, 4)]);
+        assert_eq!(i(i64::MIN, i64::MAX).difference(&i(0, i64::MAX)), vec![i(i64::MIN, -1)]);
+    }
+
+    #[test]
+    fn test_full_range_len() {
+        let mut set: IntervalSet = [i(i64::MIN, -1), i(1, i64::MAX)].into_iter().collect();
+        assert_eq!(set.len(), (1 << 64) - 1);
+        set.insert(Interval::point(0));
+        assert_eq!(set.ranges(), &[i(i64::MIN, i64::MAX)]);
+        assert_eq!(set.len(), 1 << 64);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut set: IntervalSet = [i(10, 12), i(1, 3), i(5, 6)].into_iter().collect();
+        assert_eq!(set.ranges(), &[i(1, 3), i(5, 6), i(10, 12)]);
+        assert_eq!(set.len(), 8);
+        assert_eq!(set.gaps(), vec![i(4, 4), i(7, 9)]);
+
+        // Touching 3 and 10 joins everything up to 12
+        set.insert(i(4, 9));
+        assert_eq!(set.ranges(), &[i(1, 12)]);
+        set.remove(i(3, 5));
+        set.remove(i(12, 20));
+        assert_eq!(set.ranges(), &[i(1, 2), i(6, 11)]);
+        assert!(set.includes(7));
+        assert!(!set.includes(4));
+        assert_eq!(set.to_string(), "1-2, 6-11");
+
+        let mut other = IntervalSet::new();
+        other.insert(i(3, 3));
+        other.insert(i(20, 21));
+        set.merge(&other);
+        assert_eq!(set.ranges(), &[i(1, 3), i(6, 11), i(20, 21)]);
+        set.remove(i(0, 100));
+        assert!(set.is_empty());
+    }
+}
@@ -1,31 +1,286 @@
-// mod day01;
-// mod day02;
-// mod day03;
-// mod day04;
-// mod day05;
-// mod day06;
-// mod day07;
-// mod day08;
-// mod day09;
-// mod day10;
-// mod day11;
-// mod day12;
-// mod day13;
+use std::{env, fs, process};
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
 mod day14;
+mod diagnostic;
+mod explorer;
+mod interval;
+mod memory;
+mod registry;
+mod report;
+mod rng;
+mod server;
+
+// Only counts while a measurement is running, see solutions --mem
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+const DEFAULT_PORT: u16 = 8022;
+
+const USAGE: &str = "Usage:
+  solutions              Run the latest day
+  solutions <day>        Run the given day
+  solutions [<day>] --mem
+                         Report the allocations and peak heap of parsing and each part,
+                         for every day or just the given one
+  solutions 1 --stats    Print statistics of the calories the elves are carrying
+  solutions 1 --rebalance
+                         Plan snack moves between the elves to even out what they carry
+  solutions 2 --mappings [<target>]
+                         Score the strategy guide under every reading of X, Y and Z,
+                         and list the ones that score the target
+  solutions 2 --rounds <part> [--csv]
+                         Show how each round of the strategy guide scores
+  solutions 2 --tournament [<rounds>] [<seed>]
+                         Play strategies against each other and print a leaderboard
+  solutions 3 --reorganise
+                         Plan the fewest item swaps so no item type is in both compartments
+  solutions 4 --coverage [<k>]
+                         Report the sections at least k elves clean (default 2), the ones
+                         nobody cleans and where the most elves overlap
+  solutions 4 --overlaps
+                         Show where each pair's assignments overlap, and which elf isn't needed
+  solutions 5 --crane <crane> [--cost <per lift> <per crate>]
+                         Follow the instructions with a crane, 9000, 9001 or k-per-lift, and
+                         count its lifts and the energy they take (default 1 and 1)
+  solutions 5 --after <n> [<crane>]
+                         Print the puzzle input as it would be after n instructions, with
+                         the crane (default 9000), the drawing then what's left to do
+  solutions 5 --diff [<crane>]
+                         List the stacks each instruction changes, with the crane (default 9000)
+  solutions 5 --reverse <file> [<crane>]
+                         Work out how the stacks started, from a puzzle input whose drawing
+                         shows how they ended up with the crane (default 9000)
+  solutions 5 --plan <start file> <goal file> [<crane>]
+                         Find the fewest instructions that take the stacks drawn in one file
+                         to those drawn in the other, with the crane (default 9000)
+  solutions report [--output <file>]
+                         Run every day and write a markdown progress report
+  solutions serve [--port <port>]
+                         Serve the solvers over HTTP on localhost (default port 8022)
+  solutions explore <day>
+                         Browse a day's parsed input in the terminal (days 5, 7, 11 and 14)";
 
 fn main() {
-    // day01::part1::solution();
-    // day02::solution::solution();
-    // day03::solution::solution();
-    // day04::solution::solution();
-    // day05::solution::solution();
-    // day06::solution::solution();
-    // day07::solution::solution();
-    // day08::solution::solution();
-    // day09::solution::solution();
-    // day10::solution::solution();
-    // day11::solution::solution();
-    // day12::solution::solution();
-    // day13::solution::solution();
-    day14::solution::solution();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] => {
+            let days = registry::days();
+            (days[days.len() - 1].solution)();
+        }
+        ["report"] => print!("{}", report::to_markdown(&report::run(&registry::days()))),
+        ["report", "--output", path] => {
+            let markdown = report::to_markdown(&report::run(&registry::days()));
+            fs::write(path, markdown).unwrap_or_else(|e| exit(&format!("Could not write {}: {}", path, e)));
+        }
+        ["serve"] => serve(DEFAULT_PORT),
+        ["serve", "--port", port] => match port.parse::<u16>() {
+            Ok(port) => serve(port),
+            Err(_) => exit(&format!("Invalid port '{}'", port)),
+        },
+        ["--mem"] => {
+            let profiles: Vec<memory::Profile> = registry::days().iter().flat_map(memory::profile).collect();
+            print!("{}", memory::to_table(&profiles));
+        }
+        [day, "--mem"] => match day.parse::<u8>().ok().and_then(registry::find) {
+            Some(d) => print!("{}", memory::to_table(&memory::profile(&d))),
+            None => exit(&format!("No solution registered for day '{}'", day)),
+        },
+        ["1", "--stats"] => {
+            let elves = day01::ledger::parse_ledger(day01::part1::INPUT).unwrap_or_else(|e| exit(&e.to_string()));
+            match day01::statistics::statistics(&elves) {
+                Some(stats) => print!("{}", stats),
+                None => exit("There are no elves in the ledger"),
+            }
+        }
+        ["1", "--rebalance"] => {
+            let elves = day01::ledger::parse_ledger(day01::part1::INPUT).unwrap_or_else(|e| exit(&e.to_string()));
+            print!("{}", day01::rebalance::plan(&elves));
+        }
+        ["2", "--mappings"] => mappings(None),
+        ["2", "--mappings", target] => match target.parse::<u32>() {
+            Ok(target) => mappings(Some(target)),
+            Err(_) => exit(&format!("Invalid target score '{}'", target)),
+        },
+        ["2", "--rounds", part] => rounds(part, false),
+        ["2", "--rounds", part, "--csv"] => rounds(part, true),
+        ["2", "--tournament", rest @ ..] if rest.len() <= 2 => {
+            let numbers: Result<Vec<u64>, _> = rest.iter().map(|n| n.parse::<u64>()).collect();
+            match numbers.as_deref() {
+                Ok([]) => tournament(1000, 2022),
+                Ok([rounds]) => tournament(*rounds as usize, 2022),
+                Ok([rounds, seed]) => tournament(*rounds as usize, *seed),
+                _ => exit(USAGE),
+            }
+        }
+        ["3", "--reorganise"] => match day03::reorganise::plan(day03::solution::INPUT, 3) {
+            Ok(reorganisation) => print!("{}", reorganisation),
+            Err(e) => exit(&e.to_string()),
+        },
+        ["4", "--coverage"] => coverage(2),
+        ["4", "--coverage", k] => match k.parse::<usize>() {
+            Ok(k) => coverage(k),
+            Err(_) => exit(&format!("Invalid number of elves '{}'", k)),
+        },
+        ["4", "--overlaps"] => print!("{}", day04::overlap::to_text(&day04::overlap::overlaps(&assignment_pairs()))),
+        ["5", "--crane", name] => crane(name, 1, 1),
+        ["5", "--crane", name, "--cost", per_lift, per_crate] => match (per_lift.parse::<u64>(), per_crate.parse::<u64>()) {
+            (Ok(per_lift), Ok(per_crate)) => crane(name, per_lift, per_crate),
+            _ => exit(&format!("Invalid cost '{} {}'", per_lift, per_crate)),
+        },
+        ["5", "--after", n, rest @ ..] if rest.len() <= 1 => match n.parse::<usize>() {
+            Ok(n) => after(n, rest.first().copied().unwrap_or("9000")),
+            Err(_) => exit(&format!("Invalid number of instructions '{}'", n)),
+        },
+        ["5", "--diff", rest @ ..] if rest.len() <= 1 => diffs(rest.first().copied().unwrap_or("9000")),
+        ["5", "--reverse", path, rest @ ..] if rest.len() <= 1 => reverse(path, rest.first().copied().unwrap_or("9000")),
+        ["5", "--plan", start, goal, rest @ ..] if rest.len() <= 1 => plan(start, goal, rest.first().copied().unwrap_or("9000")),
+        ["explore", day] => explore(day),
+        [day] => match day.parse::<u8>().ok().and_then(registry::find) {
+            Some(d) => (d.solution)(),
+            None => exit(&format!("No solution registered for day '{}'", day)),
+        },
+        _ => exit(USAGE),
+    }
+}
+
+fn serve(port: u16) {
+    server::serve(port).unwrap_or_else(|e| exit(&format!("Could not start server: {}", e)));
+}
+
+fn assignment_pairs() -> Vec<(day04::solution::Assignment, day04::solution::Assignment)> {
+    day04::solution::get_assignment_pairs(&day04::solution::get_lines(day04::solution::INPUT)).unwrap_or_else(|e| exit(&e.to_string()))
+}
+
+fn coverage(k: usize) {
+    let pairs = assignment_pairs();
+    let sections = pairs.iter().flat_map(|(one, two)| [one.sections, two.sections]);
+    print!("{}", day04::coverage::coverage(sections, k));
+}
+
+fn crane_or_exit(name: &str) -> Box<dyn day05::crane::Crane> {
+    day05::crane::by_name(name).unwrap_or_else(|| exit(&format!("There's no crane '{}', try 9000, 9001 or 3-per-lift", name)))
+}
+
+fn crane(name: &str, per_lift: u64, per_crate: u64) {
+    use day05::crane::{Crane, LiftCost, Metered};
+
+    let mut crane = Metered::new(crane_or_exit(name), LiftCost { per_lift, per_crate });
+    let top = day05::solution::run(day05::solution::INPUT, &mut crane);
+    println!("The {} leaves {} on top", crane.name(), top);
+    println!("{} lifts moved {} crates, using {} energy", crane.lifts, crane.crates, crane.energy);
+}
+
+fn after(n: usize, name: &str) {
+    let mut crane = crane_or_exit(name);
+    let (mut crate_stack, instructions) = day05::solution::get_inputs(day05::solution::INPUT);
+    let n = n.min(instructions.len());
+    for i in &instructions[..n] {
+        crate_stack.apply(crane.as_mut(), i).unwrap_or_else(|e| exit(&e.to_string()));
+    }
+    print!("{}", day05::render::render_puzzle(&crate_stack, &instructions[n..]));
+}
+
+fn diffs(name: &str) {
+    let crane = crane_or_exit(name);
+    let (crate_stack, instructions) = day05::solution::get_inputs(day05::solution::INPUT);
+    let mut history = day05::history::History::new(crate_stack, instructions, crane);
+    while let Some(diff) = history.redo().unwrap_or_else(|e| exit(&e.to_string())) {
+        println!("{}", diff);
+    }
+}
+
+fn reverse(path: &str, name: &str) {
+    let crane = crane_or_exit(name);
+    let input = fs::read_to_string(path).unwrap_or_else(|e| exit(&format!("Could not read {}: {}", path, e)));
+    let start = day05::reverse::reverse_input(&input, crane.as_ref()).unwrap_or_else(|e| exit(&e));
+    print!("{}", day05::render::render(&start));
+}
+
+fn drawing(path: &str) -> day05::solution::CrateStack {
+    let input = fs::read_to_string(path).unwrap_or_else(|e| exit(&format!("Could not read {}: {}", path, e)));
+    let crates = diagnostic::parse_all(&input, day05::solution::parse_drawing).unwrap_or_else(|e| exit(&format!("{}: {}", path, e)));
+    day05::solution::CrateStack::new_from_crates(crates)
+}
+
+fn plan(start: &str, goal: &str, name: &str) {
+    use day05::planner::{plan, DEFAULT_MAX_STATES};
+
+    let crane = crane_or_exit(name);
+    let instructions = plan(&drawing(start), &drawing(goal), crane.as_ref(), DEFAULT_MAX_STATES).unwrap_or_else(|e| exit(&e.to_string()));
+    print!("{}", day05::render::render_instructions(&instructions));
+}
+
+fn mappings(target: Option<u32>) {
+    let guide = day02::solution::parse_input(day02::solution::INPUT, &day02::rules::Rules::part_one()).unwrap_or_else(|e| exit(&e.to_string()));
+    let analysis = day02::analysis::analyse(&guide).unwrap_or_else(|e| exit(&e));
+    print!("{}", analysis);
+    if let Some(target) = target {
+        match analysis.matching(target).as_slice() {
+            [] => println!("Nothing scores {}", target),
+            matches => matches.iter().for_each(|e| println!("Scores {}: {}", target, e.description)),
+        }
+    }
+}
+
+fn rounds(part: &str, csv: bool) {
+    let rules = match part {
+        "1" => day02::rules::Rules::part_one(),
+        "2" => day02::rules::Rules::part_two(),
+        _ => exit(&format!("There's no part '{}', it's 1 or 2", part)),
+    };
+    let guide = day02::solution::parse_input(day02::solution::INPUT, &rules).unwrap_or_else(|e| exit(&e.to_string()));
+    let rounds = day02::breakdown::breakdown(&rules, &guide).unwrap_or_else(|e| exit(&e.to_string()));
+    match csv {
+        true => print!("{}", day02::breakdown::to_csv(&rounds)),
+        false => print!("{}", day02::breakdown::to_text(&rounds)),
+    }
+}
+
+fn tournament(rounds: usize, seed: u64) {
+    use day02::{rules::Rules, tournament::*};
+
+    let guide = day02::solution::parse_input(day02::solution::INPUT, &Rules::part_one()).unwrap_or_else(|e| exit(&e.to_string()));
+    let made_up = generate_guide(seed, guide.len());
+    let fixed = |name: &str, rules: Rules, guide: &[(char, char)]| FixedGuide::new(name, &rules, guide).unwrap_or_else(|e| exit(&e));
+    let mut strategies: Vec<Box<dyn Strategy>> = vec![
+        Box::new(fixed("guide, part one", Rules::part_one(), &guide)),
+        Box::new(fixed("guide, part two", Rules::part_two(), &guide)),
+        Box::new(fixed("made up guide", Rules::part_one(), &made_up)),
+        Box::new(Random::new(seed)),
+        Box::new(FrequencyCounter),
+        Box::new(Markov),
+    ];
+    let game = day02::rules::Game::rock_paper_scissors();
+    print!("{}", play_tournament(&game, &mut strategies, rounds));
+}
+
+fn explore(day: &str) {
+    let view = day
+        .parse::<u8>()
+        .ok()
+        .and_then(|n| registry::find(n).and_then(|d| explorer::view_for_day(n, d.input)));
+    match view {
+        Some(mut view) => explorer::run(view.as_mut()).unwrap_or_else(|e| exit(&format!("Terminal error: {}", e))),
+        None => exit(&format!("There's no explorer view for day '{}'", day)),
+    }
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
 }
@@ -0,0 +1,200 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    time::Duration,
+};
+
+use crate::registry::{Day, Solved};
+
+// Wraps the system allocator, counting what the current thread allocates while
+// a measurement is running. Outside of one it only costs a thread local lookup.
+pub struct CountingAllocator;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Usage {
+    pub allocations: usize,
+    pub bytes: usize,
+    // The most heap in use at once, over what was in use when the measurement started
+    pub peak: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    measuring: bool,
+    allocations: usize,
+    bytes: usize,
+    // Signed, memory allocated before the measurement can be freed during it
+    current: isize,
+    peak: isize,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { measuring: false, allocations: 0, bytes: 0, current: 0, peak: 0 })
+    };
+}
+
+// Counted per thread, so that other threads (like the other tests) don't get in the numbers
+fn record(allocated: usize, freed: usize) {
+    let _ = COUNTERS.try_with(|c| {
+        let mut counters = c.get();
+        if counters.measuring {
+            if allocated > 0 {
+                counters.allocations += 1;
+                counters.bytes += allocated;
+            }
+            counters.current += allocated as isize - freed as isize;
+            counters.peak = counters.peak.max(counters.current);
+            c.set(counters);
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    // A growing Vec reallocates, count it as a new allocation of the new size
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+// Run f, counting the allocations it makes on this thread
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let start = Counters { measuring: true, allocations: 0, bytes: 0, current: 0, peak: 0 };
+    COUNTERS.with(|c| c.set(start));
+    let result = f();
+    let counters = COUNTERS.with(|c| c.replace(Counters { measuring: false, ..start }));
+    let usage = Usage { allocations: counters.allocations, bytes: counters.bytes, peak: counters.peak.max(0) as usize };
+    (result, usage)
+}
+
+#[derive(Debug)]
+pub struct Profile {
+    pub day: u8,
+    // "parse", "part 1" or "part 2"
+    pub stage: String,
+    pub usage: Usage,
+    pub elapsed: Duration,
+    pub error: Option<String>,
+}
+
+// Measure the parsing and each part of a day, skipping what hasn't been written
+pub fn profile(day: &Day) -> Vec<Profile> {
+    let parse = to_profile(day.number, "parse", measure(|| day.parse(day.input)));
+    let parts = (1..=2).filter_map(|part| {
+        to_profile(day.number, &format!("part {}", part), measure(|| day.solve(part, day.input)))
+    });
+    parse.into_iter().chain(parts).collect()
+}
+
+fn to_profile(day: u8, stage: &str, (solved, usage): (Option<Solved>, Usage)) -> Option<Profile> {
+    let solved = solved?;
+    Some(Profile { day, stage: stage.to_string(), usage, elapsed: solved.elapsed, error: solved.answer.err() })
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+pub fn to_table(profiles: &[Profile]) -> String {
+    let mut lines = vec![format!(
+        "{:>3}  {:<6}  {:>11}  {:>10}  {:>10}  {:>10}",
+        "Day", "Stage", "Allocations", "Allocated", "Peak", "Time"
+    )];
+    for p in profiles {
+        let mut line = format!(
+            "{:>3}  {:<6}  {:>11}  {:>10}  {:>10}  {:>10}",
+            p.day,
+            p.stage,
+            p.usage.allocations,
+            format_bytes(p.usage.bytes),
+            format_bytes(p.usage.peak),
+            format!("{:.2?}", p.elapsed)
+        );
+        if let Some(e) = &p.error {
+            line.push_str(&format!("  failed: {}", e.split('\n').next().unwrap_or_default()));
+        }
+        lines.push(line);
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (v, usage) = measure(|| vec![0u8; 1000]);
+        assert_eq!(v.len(), 1000);
+        assert_eq!(usage, Usage { allocations: 1, bytes: 1000, peak: 1000 });
+
+        // Freeing memory brings the peak down, but not what was allocated
+        let (_, usage) = measure(|| (0..10).for_each(|_| drop(vec![0u8; 100])));
+        assert_eq!(usage, Usage { allocations: 10, bytes: 1000, peak: 100 });
+
+        // Freeing something from before the measurement doesn't count
+        let (_, usage) = measure(|| drop(v));
+        assert_eq!(usage, Usage::default());
+    }
+
+    #[test]
+    fn test_profile() {
+        let day = Day {
+            number: 1,
+            input: "1\n2\n3",
+            solution: || (),
+            parse: Some(|i| {
+                let _ = i.split('\n').collect::<Vec<&str>>();
+            }),
+            parts: [Some(|i| i.to_string()), Some(|_| panic!("Bad input"))],
+            answers: [None, None],
+        };
+        let profiles = profile(&day);
+        let stages: Vec<&str> = profiles.iter().map(|p| p.stage.as_str()).collect();
+        assert_eq!(stages, vec!["parse", "part 1", "part 2"]);
+        assert_eq!(profiles[1].usage, Usage { allocations: 1, bytes: 5, peak: 5 });
+        assert_eq!(profiles[2].error, Some("Bad input".to_string()));
+
+        let table = to_table(&profiles);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "Day  Stage   Allocations   Allocated        Peak        Time");
+        assert!(lines[2].starts_with("  1  part 1            1         5 B         5 B"));
+        assert!(lines[3].ends_with("  failed: Bad input"));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+}
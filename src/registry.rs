@@ -0,0 +1,253 @@
+use std::{
+    panic,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14,
+};
+
+// A solver takes the puzzle input and returns the answer as a string
+pub type Solver = fn(&str) -> String;
+
+// A parser reads the puzzle input and throws the result away, so that the cost
+// of parsing can be told apart from the cost of solving
+pub type Parser = fn(&str);
+
+// Day ten draws its answer, PLPAFBCL, on a screen
+pub const DAY10_SCREEN: &str = concat!(
+    "###..#....###...##..####.###...##..#....\n",
+    "#..#.#....#..#.#..#.#....#..#.#..#.#....\n",
+    "#..#.#....#..#.#..#.###..###..#....#....\n",
+    "###..#....###..####.#....#..#.#....#....\n",
+    "#....#....#....#..#.#....#..#.#..#.#....\n",
+    "#....####.#....#..#.#....###...##..####.",
+);
+
+pub struct Day {
+    pub number: u8,
+    pub input: &'static str,
+    // Prints the solution for both parts, as the day's module always did
+    pub solution: fn(),
+    // None when the parts don't share a parsing step
+    pub parse: Option<Parser>,
+    // None when a part hasn't been solved yet
+    pub parts: [Option<Solver>; 2],
+    // Answers that have been accepted, and are pinned by a test
+    pub answers: [Option<&'static str>; 2],
+}
+
+pub struct Solved {
+    // The answer, or the reason the solver gave up on the input
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+impl Day {
+    // Run a part (1 or 2) against the input. The solvers panic on bad input,
+    // so the panic is caught and its message is returned instead.
+    pub fn solve(&self, part: usize, input: &str) -> Option<Solved> {
+        let solver = self.parts.get(part.checked_sub(1)?).copied().flatten()?;
+        Some(run_caught(|| solver(input)))
+    }
+
+    // Run just the parser against the input, the answer is left empty
+    pub fn parse(&self, input: &str) -> Option<Solved> {
+        let parser = self.parse?;
+        Some(run_caught(|| {
+            parser(input);
+            String::new()
+        }))
+    }
+}
+
+fn run_caught(f: impl FnOnce() -> String + panic::UnwindSafe) -> Solved {
+    let start = Instant::now();
+    let answer = panic::catch_unwind(f).map_err(|e| {
+        e.downcast_ref::<String>()
+            .cloned()
+            .or_else(|| e.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| "Solver panicked".to_string())
+    });
+    Solved { answer, elapsed: start.elapsed() }
+}
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day {
+            number: 1,
+            input: day01::part1::INPUT,
+            solution: day01::part1::solution,
+            parse: Some(|i| {
+                day01::ledger::parse_ledger(i).unwrap_or_else(|e| panic!("{}", e));
+            }),
+            parts: [
+                Some(|i| day01::part1::part_one(i).to_string()),
+                Some(|i| day01::part1::part_two(i).to_string()),
+            ],
+            answers: [Some("71924"), Some("210406")],
+        },
+        Day {
+            number: 2,
+            input: day02::solution::INPUT,
+            solution: day02::solution::solution,
+            parse: Some(|i| {
+                day02::solution::get_guide(i, &day02::rules::Rules::part_one());
+            }),
+            parts: [
+                Some(|i| day02::solution::part1(i).to_string()),
+                Some(|i| day02::solution::part2(i).to_string()),
+            ],
+            answers: [Some("13009"), Some("10398")],
+        },
+        Day {
+            number: 3,
+            input: day03::solution::INPUT,
+            solution: day03::solution::solution,
+            parse: None,
+            parts: [
+                Some(|i| day03::solution::part1(i).to_string()),
+                Some(|i| day03::solution::part2(i).to_string()),
+            ],
+            answers: [Some("7831"), Some("2683")],
+        },
+        Day {
+            number: 4,
+            input: day04::solution::INPUT,
+            solution: day04::solution::solution,
+            parse: Some(|i| {
+                day04::solution::get_lines(i);
+            }),
+            parts: [
+                Some(|i| day04::solution::part1(&day04::solution::get_lines(i)).to_string()),
+                Some(|i| day04::solution::part2(&day04::solution::get_lines(i)).to_string()),
+            ],
+            answers: [Some("582"), Some("893")],
+        },
+        Day {
+            number: 5,
+            input: day05::solution::INPUT,
+            solution: day05::solution::solution,
+            parse: Some(|i| {
+                day05::solution::get_inputs(i);
+            }),
+            parts: [Some(day05::solution::part1), Some(day05::solution::part2)],
+            answers: [Some("CFFHVVHNC"), Some("FSZWBPTBG")],
+        },
+        Day {
+            number: 6,
+            input: day06::solution::INPUT,
+            solution: day06::solution::solution,
+            parse: None,
+            parts: [
+                Some(|i| day06::solution::part1(i).to_string()),
+                Some(|i| day06::solution::part2(i).to_string()),
+            ],
+            answers: [Some("1702"), Some("3559")],
+        },
+        Day {
+            number: 7,
+            input: day07::solution::INPUT,
+            solution: day07::solution::solution,
+            parse: Some(|i| {
+                day07::solution::get_fs(i);
+            }),
+            parts: [
+                Some(|i| day07::solution::part1(i).to_string()),
+                Some(|i| day07::solution::part2(i).to_string()),
+            ],
+            answers: [Some("1306611"), Some("13210366")],
+        },
+        Day {
+            number: 8,
+            input: day08::solution::INPUT,
+            solution: day08::solution::solution,
+            parse: Some(|i| {
+                day08::solution::get_grid(i);
+            }),
+            parts: [
+                Some(|i| day08::solution::part1(i).to_string()),
+                Some(|i| day08::solution::part2(i).to_string()),
+            ],
+            answers: [Some("1776"), Some("234416")],
+        },
+        Day {
+            number: 9,
+            input: day09::solution::INPUT,
+            solution: day09::solution::solution,
+            parse: Some(|i| {
+                day09::parser::parse_input(i);
+            }),
+            parts: [
+                Some(|i| day09::solution::part1(i).to_string()),
+                Some(|i| day09::solution::part2(i).to_string()),
+            ],
+            answers: [Some("6503"), Some("2724")],
+        },
+        Day {
+            number: 10,
+            input: day10::solution::INPUT,
+            solution: day10::solution::solution,
+            parse: Some(|i| {
+                day10::parser::parse_input(i);
+            }),
+            parts: [
+                Some(|i| day10::solution::part1(i).to_string()),
+                Some(day10::solution::screen),
+            ],
+            answers: [Some("12560"), Some(DAY10_SCREEN)],
+        },
+        Day {
+            number: 11,
+            input: day11::solution::INPUT,
+            solution: day11::solution::solution,
+            parse: Some(|i| {
+                day11::solution::parse_monkeys(i);
+            }),
+            parts: [
+                Some(|i| day11::solution::part1(i).to_string()),
+                Some(|i| day11::solution::part2(i).to_string()),
+            ],
+            answers: [Some("64032"), Some("12729522272")],
+        },
+        Day {
+            number: 12,
+            input: day12::solution::INPUT,
+            solution: day12::solution::solution,
+            parse: Some(|i| {
+                day12::solution::get_height_map(i);
+            }),
+            parts: [
+                Some(|i| day12::solution::part1(i).to_string()),
+                Some(|i| day12::solution::part2(i).to_string()),
+            ],
+            answers: [Some("391"), Some("386")],
+        },
+        Day {
+            number: 13,
+            input: "",
+            solution: day13::solution::solution,
+            parse: None,
+            parts: [None, None],
+            answers: [None, None],
+        },
+        Day {
+            number: 14,
+            input: day14::solution::INPUT,
+            solution: day14::solution::solution,
+            parse: Some(|i| {
+                day14::solution::parse_all_input(i);
+            }),
+            parts: [
+                Some(|i| day14::solution::part1(i).to_string()),
+                Some(|i| day14::solution::part2(i).to_string()),
+            ],
+            answers: [Some("843"), Some("27625")],
+        },
+    ]
+}
+
+pub fn find(number: u8) -> Option<Day> {
+    days().into_iter().find(|d| d.number == number)
+}
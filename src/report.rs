@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use crate::registry::Day;
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    // The answer matches the accepted answer
+    Verified,
+    // The answer doesn't match the accepted answer
+    Mismatch(&'static str),
+    // There's no accepted answer to compare with
+    Unverified,
+    // The solver couldn't handle the input
+    Failed(String),
+    // The part hasn't been solved yet
+    Stub,
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed: Option<Duration>,
+    pub status: Status,
+}
+
+// Run every part of every day, timing each part
+pub fn run(days: &[Day]) -> Vec<PartReport> {
+    days.iter()
+        .flat_map(|day| (0..2).map(move |idx| run_part(day, idx)))
+        .collect()
+}
+
+fn run_part(day: &Day, idx: usize) -> PartReport {
+    let part = idx as u8 + 1;
+    match day.solve(idx + 1, day.input) {
+        None => PartReport { day: day.number, part, answer: None, elapsed: None, status: Status::Stub },
+        Some(solved) => {
+            let (answer, status) = match (solved.answer, day.answers[idx]) {
+                (Err(e), _) => (None, Status::Failed(e)),
+                (Ok(a), Some(expected)) if a == expected => (Some(a), Status::Verified),
+                (Ok(a), Some(expected)) => (Some(a), Status::Mismatch(expected)),
+                (Ok(a), None) => (Some(a), Status::Unverified),
+            };
+            PartReport { day: day.number, part, answer, elapsed: Some(solved.elapsed), status }
+        }
+    }
+}
+
+// Render the answer for a table cell, multi line answers (like day ten's CRT) are kept on one row
+fn format_answer(answer: &Option<String>) -> String {
+    match answer {
+        None => "???".to_string(),
+        Some(a) if a.contains('\n') => format!("<code>{}</code>", a.split('\n').collect::<Vec<&str>>().join("<br>")),
+        Some(a) => format!("`{}`", a),
+    }
+}
+
+fn format_status(status: &Status) -> String {
+    match status {
+        Status::Verified => "yes".to_string(),
+        Status::Mismatch(expected) => format!("**no**, expected `{}`", expected),
+        Status::Unverified => "-".to_string(),
+        Status::Failed(e) => format!("**failed**: {}", e.split('\n').next().unwrap_or_default()),
+        Status::Stub => "stub".to_string(),
+    }
+}
+
+pub fn to_markdown(reports: &[PartReport]) -> String {
+    let mut lines = vec![
+        "# Advent of Code 2022 progress".to_string(),
+        String::new(),
+        "| Day | Part | Answer | Time | Verified |".to_string(),
+        "|----:|-----:|--------|-----:|----------|".to_string(),
+    ];
+
+    for r in reports {
+        let elapsed = r.elapsed.map(|e| format!("{:.2?}", e)).unwrap_or_else(|| "-".to_string());
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} |",
+            r.day,
+            r.part,
+            format_answer(&r.answer),
+            elapsed,
+            format_status(&r.status)
+        ));
+    }
+
+    let mut stubs: Vec<String> = reports
+        .iter()
+        .filter(|r| r.status == Status::Stub)
+        .map(|r| format!("day{:02}", r.day))
+        .collect();
+    stubs.dedup();
+
+    lines.push(String::new());
+    if stubs.is_empty() {
+        lines.push("Every day has been solved.".to_string());
+    } else {
+        lines.push(format!("Still to do: {}", stubs.join(", ")));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_days() -> Vec<Day> {
+        vec![
+            Day {
+                number: 1,
+                input: "1\n2\n3",
+                solution: || (),
+                parse: None,
+                parts: [
+                    Some(|i| i.split('\n').count().to_string()),
+                    Some(|i| i.replace('\n', "")),
+                ],
+                answers: [Some("3"), Some("321")],
+            },
+            Day {
+                number: 2,
+                input: "",
+                solution: || (),
+                parse: None,
+                parts: [None, None],
+                answers: [None, None],
+            },
+            Day {
+                number: 3,
+                input: "ab",
+                solution: || (),
+                parse: None,
+                parts: [Some(|i| i.to_string()), Some(|_| "#.\n.#".to_string())],
+                answers: [None, None],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_run() {
+        let reports = run(&get_days());
+        let statuses: Vec<&Status> = reports.iter().map(|r| &r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                &Status::Verified,
+                &Status::Mismatch("321"),
+                &Status::Stub,
+                &Status::Stub,
+                &Status::Unverified,
+                &Status::Unverified,
+            ]
+        );
+        assert_eq!(reports[1].answer, Some("123".to_string()));
+        assert!(reports[2].elapsed.is_none());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let markdown = to_markdown(&run(&get_days()));
+        let lines: Vec<&str> = markdown.split('\n').collect();
+        assert_eq!(lines[2], "| Day | Part | Answer | Time | Verified |");
+        assert!(lines[4].starts_with("| 1 | 1 | `3` | "));
+        assert!(lines[4].ends_with(" | yes |"));
+        assert!(lines[5].ends_with(" | **no**, expected `321` |"));
+        assert_eq!(lines[6], "| 2 | 1 | ??? | - | stub |");
+        assert!(lines[9].starts_with("| 3 | 2 | <code>#.<br>.#</code> | "));
+        assert_eq!(lines[11], "Still to do: day02");
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use crate::registry::{self, Day};
+
+// The largest body accepted, the puzzle inputs are all well under this
+const MAX_BODY: usize = 1 << 20;
+
+// The most the request line and headers can take up together
+const MAX_HEAD: usize = 8 << 10;
+
+// How long a client can go quiet before its connection is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+// A very small HTTP/1.1 server, a thread for each connection, so the dashboard
+// can run the solvers. Endpoints:
+//   GET  /days                 list the registered days
+//   POST /days/{n}/parts/{p}   solve a part, the body is the puzzle input.
+//                              An empty body solves the bundled input.
+pub fn serve(port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("Listening on http://{}", listener.local_addr()?);
+    run(listener);
+    Ok(())
+}
+
+pub fn run(listener: TcpListener) {
+    let days = Arc::new(registry::days());
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Error accepting connection: {}", e);
+                continue;
+            }
+        };
+        let days = Arc::clone(&days);
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &days) {
+                eprintln!("Error handling connection: {}", e);
+            }
+        });
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn new(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::new(status, format!("{{\"error\":{}}}", json_string(message)))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, days: &[Day]) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = match read_request(&mut reader)? {
+        Ok((method, path, body)) => route(days, &method, &path, &body),
+        Err(response) => response,
+    };
+    write_response(&stream, &response)?;
+    // Read what's left of a request that was turned away before closing, closing with
+    // it unread resets the connection and the client can lose the response
+    stream.shutdown(Shutdown::Write)?;
+    let _ = io::copy(&mut reader.take(MAX_BODY as u64), &mut io::sink());
+    Ok(())
+}
+
+// Read the request line, headers and body. The outer error is for IO, the
+// inner error is the response for requests that aren't valid HTTP, or are too big.
+fn read_request(
+    reader: &mut impl BufRead,
+) -> io::Result<Result<(String, String, String), Response>> {
+    let mut head_left = MAX_HEAD;
+    let request_line = match read_head_line(reader, &mut head_left)? {
+        Some(line) => line,
+        None => return Ok(Err(Response::error(400, "The request line is too long"))),
+    };
+    let parts: Vec<&str> = request_line.split_whitespace().collect();
+    if parts.len() != 3 {
+        return Ok(Err(Response::error(400, "Malformed request line")));
+    }
+
+    let mut content_length = 0;
+    loop {
+        let header = match read_head_line(reader, &mut head_left)? {
+            Some(header) => header,
+            None => {
+                let message = format!("The headers can't be more than {} bytes", MAX_HEAD);
+                return Ok(Err(Response::error(431, &message)));
+            }
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                match value.trim().parse::<usize>() {
+                    Ok(n) => content_length = n,
+                    Err(_) => return Ok(Err(Response::error(400, "Invalid Content-Length"))),
+                }
+            }
+        }
+    }
+
+    if content_length > MAX_BODY {
+        let message = format!("The body can't be more than {} bytes", MAX_BODY);
+        return Ok(Err(Response::error(413, &message)));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    match String::from_utf8(body) {
+        Ok(body) => Ok(Ok((parts[0].to_string(), parts[1].to_string(), body))),
+        Err(_) => Ok(Err(Response::error(400, "The body must be UTF-8"))),
+    }
+}
+
+// A line of the request line or headers, None once it's gone past what's left of MAX_HEAD
+fn read_head_line(reader: &mut impl BufRead, head_left: &mut usize) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    let read = reader.by_ref().take(*head_left as u64).read_line(&mut line)?;
+    if read == *head_left && !line.ends_with('\n') {
+        return Ok(None);
+    }
+    *head_left -= read;
+    Ok(Some(line))
+}
+
+fn write_response(mut stream: &TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn route(days: &[Day], method: &str, path: &str, body: &str) -> Response {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        ("GET", ["days"]) => list_days(days),
+        ("POST", ["days", day, "parts", part]) => match (day.parse::<u8>(), part.parse::<usize>()) {
+            (Ok(day), Ok(part)) => solve(days, day, part, body),
+            _ => Response::error(400, "The day and part must be numbers"),
+        },
+        (_, ["days"]) | (_, ["days", _, "parts", _]) => Response::error(405, "Method not allowed"),
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+fn list_days(days: &[Day]) -> Response {
+    let entries: Vec<String> = days
+        .iter()
+        .map(|d| {
+            let parts: Vec<String> = d
+                .parts
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.is_some())
+                .map(|(idx, _)| (idx + 1).to_string())
+                .collect();
+            format!("{{\"day\":{},\"parts\":[{}]}}", d.number, parts.join(","))
+        })
+        .collect();
+    Response::new(200, format!("{{\"days\":[{}]}}", entries.join(",")))
+}
+
+fn solve(days: &[Day], number: u8, part: usize, body: &str) -> Response {
+    let day = match days.iter().find(|d| d.number == number) {
+        Some(d) => d,
+        None => return Response::error(404, &format!("No solution registered for day {}", number)),
+    };
+    let input = if body.is_empty() { day.input } else { body };
+    let solved = match day.solve(part, input) {
+        Some(s) => s,
+        None => return Response::error(404, &format!("Day {} part {} hasn't been solved", number, part)),
+    };
+
+    let elapsed_ms = solved.elapsed.as_secs_f64() * 1000.0;
+    let (status, answer, diagnostics) = match solved.answer {
+        Ok(answer) => (200, json_string(&answer), String::new()),
+        Err(diagnostic) => (422, "null".to_string(), json_string(&diagnostic)),
+    };
+    Response::new(
+        status,
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ms\":{:.3},\"diagnostics\":[{}]}}",
+            number, part, answer, elapsed_ms, diagnostics
+        ),
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Read;
+
+    fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || run(listener));
+        addr
+    }
+
+    fn request(addr: &str, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            addr,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+
+    #[test]
+    fn test_solve() {
+        let addr = start_server();
+        let input = "A Y\nB X\nC Z\n";
+        let (status, body) = request(&addr, "POST", "/days/2/parts/1", input);
+        assert_eq!(status, 200);
+        assert!(body.starts_with("{\"day\":2,\"part\":1,\"answer\":\"15\",\"elapsed_ms\":"));
+        assert!(body.ends_with(",\"diagnostics\":[]}"));
+
+        let (status, body) = request(&addr, "POST", "/days/2/parts/2", input);
+        assert_eq!(status, 200);
+        assert!(body.contains("\"answer\":\"12\""));
+    }
+
+    #[test]
+    fn test_errors() {
+        let addr = start_server();
+        let (status, body) = request(&addr, "POST", "/days/2/parts/1", "not a strategy guide");
+        assert_eq!(status, 422);
+        assert!(body.contains("\"answer\":null"));
+        assert!(!body.contains("\"diagnostics\":[]"));
+
+        assert_eq!(request(&addr, "POST", "/days/13/parts/1", "").0, 404);
+        assert_eq!(request(&addr, "POST", "/days/26/parts/1", "").0, 404);
+        assert_eq!(request(&addr, "POST", "/days/2/parts/3", "").0, 404);
+        assert_eq!(request(&addr, "POST", "/days/two/parts/1", "").0, 400);
+        assert_eq!(request(&addr, "GET", "/days/2/parts/1", "").0, 405);
+        assert_eq!(request(&addr, "GET", "/nothing", "").0, 404);
+    }
+
+    #[test]
+    fn test_too_big() {
+        let addr = start_server();
+        let mut stream = TcpStream::connect(&addr).unwrap();
+        write!(stream, "POST /days/2/parts/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n", usize::MAX).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"), "{}", response);
+    }
+
+    #[test]
+    fn test_head_too_big() {
+        let addr = start_server();
+        let respond = |head: String| {
+            let mut stream = TcpStream::connect(&addr).unwrap();
+            write!(stream, "{}\r\n\r\n", head).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let path = "a".repeat(MAX_HEAD);
+        let response = respond(format!("GET /{} HTTP/1.1", path));
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"), "{}", response);
+
+        let headers = "X-Padding: a\r\n".repeat(MAX_HEAD / 10);
+        let response = respond(format!("GET /days HTTP/1.1\r\n{}", headers));
+        assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"), "{}", response);
+
+        // A long header that fits is fine
+        let response = respond(format!("GET /days HTTP/1.1\r\nX-Padding: {}", "a".repeat(MAX_HEAD / 2)));
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    }
+
+    #[test]
+    fn test_idle_client() {
+        let addr = start_server();
+        // Connects and never sends anything, the next request still gets an answer
+        let _idle = TcpStream::connect(&addr).unwrap();
+        assert_eq!(request(&addr, "POST", "/days/2/parts/1", "A Y\n").0, 200);
+    }
+
+    #[test]
+    fn test_list_days() {
+        let addr = start_server();
+        let (status, body) = request(&addr, "GET", "/days", "");
+        assert_eq!(status, 200);
+        assert!(body.contains("{\"day\":1,\"parts\":[1,2]}"));
+        assert!(body.contains("{\"day\":13,\"parts\":[]}"));
+    }
+}